@@ -0,0 +1,232 @@
+use clap::{Parser, Subcommand};
+use league_auto_accept::champion_catalog::{self, ChampionSummary};
+use league_auto_accept::config_store;
+use league_auto_accept::config_validation;
+use league_auto_accept::events::{self, EventSink};
+use league_auto_accept::league_client::{AutoAcceptService, ChampSelectConfig, LeagueClient, LeagueError, WriteOutcome};
+use league_auto_accept::logging;
+use league_auto_accept::paths::AppPaths;
+use league_auto_accept::webhooks::Webhooks;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::process::ExitCode;
+
+/// Auto-accept without the desktop window. Uses the same config and history
+/// as the app.
+#[derive(Parser)]
+#[command(name = "league-auto-accept-cli", version)]
+struct Cli {
+    /// Print one JSON object per line instead of text.
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Show whether the League client is running and what it is doing.
+    Status,
+    /// Accept the current ready check.
+    Accept,
+    /// List champions by ID.
+    Champions {
+        /// Only champions this account owns.
+        #[arg(long)]
+        owned: bool,
+    },
+    /// Show or change the saved configuration.
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommand,
+    },
+    /// Run auto-accept, pick and ban with the saved configuration until interrupted.
+    Watch,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    Show,
+    /// Set one setting by its dotted path, e.g. `ready_check.accept_delay_secs 3`
+    /// or `pick_priority '["Jinx", "Ashe"]'`. Values are parsed as JSON, falling
+    /// back to a plain string.
+    Set { key: String, value: String },
+}
+
+struct Output {
+    json: bool,
+}
+
+impl Output {
+    /// Prints `value` as a JSON line, or `text` for humans.
+    fn print<T: Serialize>(&self, value: &T, text: impl FnOnce() -> String) {
+        if self.json {
+            println!("{}", json!(value));
+        } else {
+            println!("{}", text());
+        }
+    }
+}
+
+/// Prints the monitoring loop's events as they happen, and passes them on to
+/// the configured webhooks.
+struct ConsoleEvents {
+    json: bool,
+    webhooks: Webhooks,
+}
+
+impl EventSink for ConsoleEvents {
+    fn send(&self, event: &str, payload: Value) {
+        self.webhooks.notify(event, &payload);
+
+        let now = chrono::Local::now();
+        if self.json {
+            println!("{}", json!({ "timestamp": now.to_rfc3339(), "event": event, "payload": payload }));
+        } else {
+            println!("[{}] {}: {}", now.format("%H:%M:%S"), event, events::describe(&payload));
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let output = Output { json: cli.json };
+
+    match run(cli.command, &output).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            if output.json {
+                println!("{}", json!({ "error": e.to_string() }));
+            } else {
+                eprintln!("Error: {}", e);
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(command: Command, output: &Output) -> Result<(), LeagueError> {
+    let paths = AppPaths::standalone()?;
+    champion_catalog::set_cache_dir(paths.cache_dir.clone());
+    if let Err(e) = logging::init(&paths.log_dir, logging::DEFAULT_LEVEL) {
+        eprintln!("Could not set up logging: {}", e);
+    }
+    let config = config_store::load(&paths.config_file());
+    if let Err(e) = logging::set_level(&config.app.log_level) {
+        tracing::warn!("Keeping the default log level: {}", e);
+    }
+
+    match command {
+        Command::Status => status(output).await,
+        Command::Accept => accept(output).await,
+        Command::Champions { owned } => champions(owned, output).await,
+        Command::Config { action: ConfigCommand::Show } => {
+            output.print(&config, || serde_json::to_string_pretty(&config).unwrap_or_default());
+            Ok(())
+        }
+        Command::Config { action: ConfigCommand::Set { key, value } } => set_config(&paths, config, &key, &value, output).await,
+        Command::Watch => watch(paths, config, output).await,
+    }
+}
+
+async fn status(output: &Output) -> Result<(), LeagueError> {
+    let client = match LeagueClient::new().await {
+        Ok(client) => client,
+        Err(e) => {
+            output.print(&json!({ "connected": false, "error": e.to_string() }), || format!("Not connected: {}", e));
+            return Ok(());
+        }
+    };
+
+    let name = client.get_summoner_name().await?;
+    let phase = client.get_gameflow_phase().await?;
+    let queue_id = client.get_current_queue_id().await.unwrap_or(None);
+
+    output.print(
+        &json!({ "connected": true, "summoner": name, "phase": phase, "queue_id": queue_id }),
+        || match queue_id {
+            Some(queue_id) => format!("Connected as {}\nPhase: {} (queue {})", name, phase, queue_id),
+            None => format!("Connected as {}\nPhase: {}", name, phase),
+        },
+    );
+    Ok(())
+}
+
+async fn accept(output: &Output) -> Result<(), LeagueError> {
+    let client = LeagueClient::new().await?;
+    if let WriteOutcome::Rejected(reason) = client.accept_ready_check().await? {
+        return Err(format!("Failed to accept match (no ready check active?): {}", reason).into());
+    }
+
+    output.print(&json!({ "accepted": true }), || "Match accepted".to_string());
+    Ok(())
+}
+
+async fn champions(owned_only: bool, output: &Output) -> Result<(), LeagueError> {
+    let client = LeagueClient::new().await;
+
+    let mut champions: Vec<ChampionSummary> = match &client {
+        Ok(client) => client.get_all_champions().await?,
+        Err(_) if !owned_only => champion_catalog::fallback_catalog().summaries(),
+        Err(e) => return Err(e.to_string().into()),
+    };
+
+    if let (true, Ok(client)) = (owned_only, &client) {
+        let owned: HashSet<i64> = client.get_available_champions().await?
+            .iter()
+            .filter_map(|champ| champ.get("id").and_then(Value::as_i64))
+            .collect();
+        champions.retain(|champion| owned.contains(&champion.id));
+    }
+
+    for champion in &champions {
+        output.print(champion, || format!("{:>4}  {}", champion.id, champion.name));
+    }
+    Ok(())
+}
+
+async fn set_config(paths: &AppPaths, config: ChampSelectConfig, key: &str, raw: &str, output: &Output) -> Result<(), LeagueError> {
+    let pointer = format!("/{}", key.replace('.', "/"));
+    let mut new_value: Value = serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()));
+
+    // Champion lists can also be given as "Jinx, Ashe".
+    if let (true, Value::String(list)) = (config_store::CHAMPION_LISTS.contains(&pointer.as_str()), &new_value) {
+        new_value = list.split(',').map(|name| name.trim()).collect();
+    }
+
+    let mut value = serde_json::to_value(&config).map_err(|e| format!("Could not serialize config: {}", e))?;
+    let slot = value.pointer_mut(&pointer).ok_or_else(|| format!("Unknown setting '{}'", key))?;
+    *slot = new_value;
+    let catalog = champion_catalog::live_or_fallback().await;
+    config_validation::resolve_champion_lists(&mut value, &catalog)?;
+    let saved_value = value.pointer(&pointer).cloned().unwrap_or_default();
+
+    let updated: ChampSelectConfig = serde_json::from_value(value)
+        .map_err(|e| format!("Invalid value for {}: {}", key, e))?;
+    updated.ready_check.validate()?;
+    for webhook in &updated.webhooks {
+        webhook.validate()?;
+    }
+    config_store::save(&paths.config_file(), &updated)?;
+
+    output.print(&json!({ "key": key, "value": saved_value }), || format!("{} = {}", key, saved_value));
+    Ok(())
+}
+
+async fn watch(paths: AppPaths, config: ChampSelectConfig, output: &Output) -> Result<(), LeagueError> {
+    let mut service = AutoAcceptService::new(paths).await?;
+    let events = ConsoleEvents { json: output.json, webhooks: Webhooks::new(config.webhooks.clone()) };
+    service.update_config(config);
+
+    if !output.json {
+        eprintln!("Watching the League client, press Ctrl+C to stop");
+    }
+
+    tokio::select! {
+        result = service.start_monitoring(&events) => result,
+        _ = tokio::signal::ctrl_c() => Ok(()),
+    }
+}
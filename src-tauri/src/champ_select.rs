@@ -0,0 +1,275 @@
+use crate::counter_picks::EnemyPick;
+use serde::{Deserialize, Serialize};
+
+/// `/lol-champ-select/v1/session`. Only the fields we use are modelled; the
+/// client adds fields between patches, so unknown ones are ignored and
+/// missing ones fall back to their defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampSelectSession {
+    pub game_id: i64,
+    /// -1 when we are not a player in this champion select (e.g. spectating).
+    pub local_player_cell_id: i64,
+    /// Turns in order; each turn holds the actions taken simultaneously.
+    pub actions: Vec<Vec<ChampSelectAction>>,
+    pub my_team: Vec<TeamMember>,
+    pub their_team: Vec<TeamMember>,
+    pub bans: Bans,
+    pub timer: Timer,
+    pub trades: Vec<Trade>,
+    pub bench_enabled: bool,
+    pub bench_champions: Vec<BenchChampion>,
+    pub is_custom_game: bool,
+}
+
+impl Default for ChampSelectSession {
+    fn default() -> Self {
+        Self {
+            game_id: 0,
+            local_player_cell_id: -1,
+            actions: Vec::new(),
+            my_team: Vec::new(),
+            their_team: Vec::new(),
+            bans: Bans::default(),
+            timer: Timer::default(),
+            trades: Vec::new(),
+            bench_enabled: false,
+            bench_champions: Vec::new(),
+            is_custom_game: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionType {
+    Pick,
+    Ban,
+    TenBansReveal,
+    #[default]
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampSelectAction {
+    pub id: i64,
+    pub actor_cell_id: i64,
+    #[serde(rename = "type")]
+    pub action_type: ActionType,
+    /// Hovered or locked-in champion, 0 for none.
+    pub champion_id: i64,
+    pub completed: bool,
+    pub is_in_progress: bool,
+    pub is_ally_action: bool,
+}
+
+impl Default for ChampSelectAction {
+    fn default() -> Self {
+        Self {
+            id: -1,
+            actor_cell_id: -1,
+            action_type: ActionType::Other,
+            champion_id: 0,
+            completed: false,
+            is_in_progress: false,
+            is_ally_action: false,
+        }
+    }
+}
+
+impl ChampSelectAction {
+    /// Our turn, and nothing locked in yet.
+    pub fn is_actionable(&self) -> bool {
+        self.is_in_progress && !self.completed
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TeamMember {
+    pub cell_id: i64,
+    pub champion_id: i64,
+    pub champion_pick_intent: i64,
+    /// `top`, `jungle`, `middle`, `bottom`, `utility`, or empty in blind pick
+    /// and for enemies in most queues.
+    pub assigned_position: String,
+    pub summoner_id: i64,
+    pub puuid: String,
+    pub game_name: String,
+    pub tag_line: String,
+    pub team: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Bans {
+    pub my_team_bans: Vec<i64>,
+    pub their_team_bans: Vec<i64>,
+    pub num_bans: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Timer {
+    /// `PLANNING`, `BAN_PICK`, `FINALIZATION` or `GAME_STARTING`.
+    pub phase: String,
+    /// Newer clients report this; older ones only `timeLeftInPhase`.
+    pub adjusted_time_left_in_phase: Option<i64>,
+    pub time_left_in_phase: Option<i64>,
+    pub total_time_in_phase: i64,
+    pub is_infinite: bool,
+}
+
+impl Timer {
+    pub fn time_left_ms(&self) -> i64 {
+        self.adjusted_time_left_in_phase.or(self.time_left_in_phase).unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Trade {
+    pub id: i64,
+    pub cell_id: i64,
+    /// `AVAILABLE`, `BUSY`, `INVALID`, `RECEIVED` or `SENT`.
+    pub state: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BenchChampion {
+    pub champion_id: i64,
+    pub is_priority: bool,
+}
+
+impl ChampSelectSession {
+    pub fn is_player(&self) -> bool {
+        self.local_player_cell_id >= 0
+    }
+
+    pub fn local_player(&self) -> Option<&TeamMember> {
+        self.my_team.iter().find(|member| member.cell_id == self.local_player_cell_id)
+    }
+
+    /// Our assigned position, if the queue has one.
+    pub fn my_position(&self) -> Option<&str> {
+        self.local_player()
+            .map(|member| member.assigned_position.as_str())
+            .filter(|position| !position.is_empty())
+    }
+
+    /// Our own actions, in turn order.
+    pub fn my_actions(&self) -> impl Iterator<Item = &ChampSelectAction> {
+        self.actions
+            .iter()
+            .flatten()
+            .filter(move |action| action.actor_cell_id == self.local_player_cell_id)
+    }
+
+    /// Enemy champions that are hovered or locked in.
+    pub fn enemy_picks(&self) -> Vec<EnemyPick> {
+        self.their_team
+            .iter()
+            .filter(|member| member.champion_id > 0)
+            .map(|member| EnemyPick {
+                champion_id: member.champion_id,
+                position: Some(member.assigned_position.clone()).filter(|p| !p.is_empty()),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! fixture {
+        ($name:literal) => {
+            serde_json::from_str::<ChampSelectSession>(include_str!(concat!("../tests/fixtures/champ_select/", $name, ".json")))
+                .expect(concat!("fixture ", $name))
+        };
+    }
+
+    #[test]
+    fn draft_ban_phase() {
+        let session = fixture!("draft_ban_phase");
+
+        assert_eq!(session.game_id, 7_123_456_789);
+        assert_eq!(session.local_player_cell_id, 2);
+        assert_eq!(session.my_position(), Some("middle"));
+        assert_eq!(session.timer.phase, "BAN_PICK");
+        assert_eq!(session.timer.time_left_ms(), 27_412);
+        assert_eq!(session.bans.num_bans, 10);
+
+        let mine: Vec<_> = session.my_actions().collect();
+        assert_eq!(mine.len(), 2);
+        assert_eq!(mine[0].action_type, ActionType::Ban);
+        assert!(mine[0].is_actionable());
+        assert_eq!(mine[1].action_type, ActionType::Pick);
+        assert!(!mine[1].is_actionable());
+
+        // The reveal step between ban and pick turns is not ours and has an
+        // action type we don't act on.
+        assert!(session.actions.iter().flatten().any(|a| a.action_type == ActionType::TenBansReveal));
+        assert!(session.enemy_picks().is_empty());
+    }
+
+    #[test]
+    fn draft_pick_phase_with_enemy_picks() {
+        let session = fixture!("draft_pick_phase");
+
+        assert_eq!(session.my_position(), Some("bottom"));
+        assert_eq!(session.bans.my_team_bans, vec![157, 238, 11, 555, 84]);
+        assert_eq!(session.bans.their_team_bans.len(), 5);
+
+        let pick = session.my_actions().find(|a| a.action_type == ActionType::Pick).unwrap();
+        assert!(pick.is_actionable());
+        assert_eq!(pick.champion_id, 222);
+        assert_eq!(pick.id, 14);
+
+        let enemies = session.enemy_picks();
+        assert_eq!(enemies.len(), 2);
+        assert_eq!(enemies[0].champion_id, 145);
+        assert_eq!(enemies[0].position, None);
+        assert_eq!(session.trades.len(), 4);
+        assert_eq!(session.trades[0].state, "INVALID");
+    }
+
+    #[test]
+    fn aram_bench_and_trades() {
+        let session = fixture!("aram_bench");
+
+        assert!(session.bench_enabled);
+        assert_eq!(session.bench_champions.len(), 3);
+        assert!(session.bench_champions[0].is_priority);
+        assert_eq!(session.my_position(), None);
+        assert!(session.my_actions().next().is_none());
+        assert_eq!(session.local_player().map(|m| m.champion_id), Some(99));
+        assert_eq!(session.trades.iter().filter(|t| t.state == "AVAILABLE").count(), 2);
+        // Older clients only send timeLeftInPhase.
+        assert_eq!(session.timer.time_left_ms(), 51_000);
+    }
+
+    #[test]
+    fn tolerates_missing_and_unknown_fields() {
+        let session: ChampSelectSession = serde_json::from_str(
+            r#"{
+                "localPlayerCellId": 0,
+                "someFutureField": {"nested": [1, 2, 3]},
+                "actions": [[{"id": 1, "actorCellId": 0, "type": "vote", "isInProgress": true, "newFlag": true}]],
+                "myTeam": [{"cellId": 0}]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(session.game_id, 0);
+        assert_eq!(session.my_actions().next().map(|a| a.action_type), Some(ActionType::Other));
+        assert_eq!(session.timer.time_left_ms(), 0);
+        assert!(session.trades.is_empty());
+
+        let empty: ChampSelectSession = serde_json::from_str("{}").unwrap();
+        assert!(!empty.is_player());
+    }
+}
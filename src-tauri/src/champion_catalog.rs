@@ -0,0 +1,531 @@
+use crate::league_client::{LeagueClient, LeagueError};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+pub const CATALOG_CACHE_FILE: &str = "champion_catalog.json";
+/// A dataset refreshed from Data Dragon, used instead of the bundled one when present.
+pub const DATASET_OVERRIDE_FILE: &str = "champion_dataset.json";
+
+const BUNDLED_DATASET: &str = include_str!("../data/champions.json");
+
+static CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
+static FALLBACK_CATALOG: Mutex<Option<Arc<ChampionCatalog>>> = Mutex::new(None);
+
+/// Sets the directory the catalog is persisted to. Only the first call has an effect.
+pub fn set_cache_dir(dir: PathBuf) {
+    let _ = CACHE_DIR.set(dir);
+}
+
+pub fn cache_path() -> Option<PathBuf> {
+    CACHE_DIR.get().map(|dir| dir.join(CATALOG_CACHE_FILE))
+}
+
+pub fn dataset_override_path() -> Option<PathBuf> {
+    CACHE_DIR.get().map(|dir| dir.join(DATASET_OVERRIDE_FILE))
+}
+
+/// Catalog built from the current dataset, used when the client has no game data.
+pub fn fallback_catalog() -> Arc<ChampionCatalog> {
+    let mut fallback = FALLBACK_CATALOG.lock().unwrap();
+    fallback
+        .get_or_insert_with(|| Arc::new(ChampionDataset::current().to_catalog()))
+        .clone()
+}
+
+/// The running client's catalog, so champions newer than the bundled data
+/// resolve too. The fallback when no client is running or it has no game data.
+pub async fn live_or_fallback() -> Arc<ChampionCatalog> {
+    let Ok(client) = LeagueClient::new().await else {
+        return fallback_catalog();
+    };
+    match client.champion_catalog().await {
+        Ok(catalog) => Arc::new(catalog.clone()),
+        Err(_) => fallback_catalog(),
+    }
+}
+
+/// Replaces the bundled dataset with one built from a local Data Dragon `champion.json`.
+pub fn refresh_dataset_from_file(path: &Path) -> Result<ChampionDataset, LeagueError> {
+    let content = fs::read_to_string(path)?;
+    let json: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e))?;
+
+    let dataset = ChampionDataset::from_data_dragon(&json, &ChampionDataset::current())?;
+    let override_path = dataset_override_path().ok_or("Champion data directory is not set")?;
+    dataset.save(&override_path)?;
+
+    *FALLBACK_CATALOG.lock().unwrap() = None;
+    Ok(dataset)
+}
+
+/// One entry of the champion dataset shipped with the app.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChampionRecord {
+    pub id: i64,
+    /// Internal key, e.g. `MonkeyKing`.
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChampionDataset {
+    pub version: String,
+    pub champions: Vec<ChampionRecord>,
+}
+
+impl ChampionDataset {
+    pub fn bundled() -> Self {
+        serde_json::from_str(BUNDLED_DATASET).expect("bundled champion dataset is valid JSON")
+    }
+
+    /// The refreshed dataset if one was saved, otherwise the bundled one.
+    pub fn current() -> Self {
+        dataset_override_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_else(Self::bundled)
+    }
+
+    /// Builds a dataset from a Data Dragon `champion.json`, keeping the aliases
+    /// of champions already known to `existing`.
+    pub fn from_data_dragon(json: &serde_json::Value, existing: &ChampionDataset) -> Result<Self, LeagueError> {
+        let data = json
+            .get("data")
+            .and_then(|d| d.as_object())
+            .ok_or("Not a Data Dragon champion.json: missing \"data\" object")?;
+
+        let mut champions = Vec::with_capacity(data.len());
+        for (key, entry) in data {
+            let id: i64 = entry
+                .get("key")
+                .and_then(|k| k.as_str())
+                .and_then(|k| k.parse().ok())
+                .ok_or_else(|| format!("Champion {} has no numeric key", key))?;
+            let name = entry.get("name").and_then(|n| n.as_str()).unwrap_or(key).to_string();
+            let aliases = existing
+                .champions
+                .iter()
+                .find(|c| c.id == id)
+                .map(|c| c.aliases.clone())
+                .unwrap_or_default();
+
+            champions.push(ChampionRecord { id, key: key.clone(), name, aliases });
+        }
+
+        if champions.is_empty() {
+            return Err("Data Dragon champion.json contains no champions".into());
+        }
+        champions.sort_by_key(|c| c.id);
+
+        let version = json.get("version").and_then(|v| v.as_str()).unwrap_or("unknown").to_string();
+        Ok(Self { version, champions })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), LeagueError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Could not serialize champion dataset: {}", e))?;
+        fs::write(path, content)?;
+
+        Ok(())
+    }
+
+    pub fn to_catalog(&self) -> ChampionCatalog {
+        let champions = self
+            .champions
+            .iter()
+            .map(|record| ChampionInfo {
+                id: record.id,
+                alias: record.key.clone(),
+                name: record.name.clone(),
+                aliases: record.aliases.clone(),
+                roles: Vec::new(),
+                localized_name: record.name.clone(),
+            })
+            .collect();
+
+        ChampionCatalog::new(self.version.clone(), String::new(), champions)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChampionInfo {
+    pub id: i64,
+    /// Internal name, e.g. `MonkeyKing` for Wukong.
+    pub alias: String,
+    /// English display name.
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub roles: Vec<String>,
+    /// Name as shown by the client in its current language.
+    #[serde(default)]
+    pub localized_name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChampionSummary {
+    pub id: i64,
+    pub name: String,
+    pub alias: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChampionCatalog {
+    /// Client game version the catalog was built for.
+    pub version: String,
+    #[serde(default)]
+    pub locale: String,
+    pub champions: Vec<ChampionInfo>,
+    #[serde(skip)]
+    by_id: HashMap<i64, usize>,
+    /// Normalized name, alias, localized name and nicknames -> champion indices.
+    #[serde(skip)]
+    by_name: HashMap<String, Vec<usize>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ResolveError {
+    NotFound { query: String, suggestions: Vec<String> },
+    Ambiguous { query: String, candidates: Vec<String> },
+}
+
+impl std::fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ResolveError::NotFound { query, suggestions } if suggestions.is_empty() => {
+                write!(f, "Unknown champion '{}'", query)
+            }
+            ResolveError::NotFound { query, suggestions } => {
+                write!(f, "Unknown champion '{}' (did you mean {}?)", query, suggestions.join(", "))
+            }
+            ResolveError::Ambiguous { query, candidates } => {
+                write!(f, "'{}' matches several champions: {}", query, candidates.join(", "))
+            }
+        }
+    }
+}
+
+/// How a typed name was matched to a champion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    /// Name, internal alias, localized name or nickname.
+    Exact,
+    /// The only champion starting with the query.
+    Prefix,
+    /// The closest name within a small edit distance.
+    Fuzzy,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ChampionMatch<'a> {
+    pub champion: &'a ChampionInfo,
+    pub kind: MatchKind,
+}
+
+/// Lowercases and drops everything but letters and digits, so "Kai'Sa",
+/// "kaisa" and "KAI SA" compare equal.
+pub fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// Edit distance counting an adjacent transposition ("yasou" -> "yasuo") as one edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+impl ChampionCatalog {
+    pub fn new(version: String, locale: String, champions: Vec<ChampionInfo>) -> Self {
+        let mut catalog = Self {
+            version,
+            locale,
+            champions,
+            by_id: HashMap::new(),
+            by_name: HashMap::new(),
+        };
+        catalog.build_index();
+        catalog
+    }
+
+    fn build_index(&mut self) {
+        self.by_id.clear();
+        self.by_name.clear();
+
+        for (index, champion) in self.champions.iter().enumerate() {
+            self.by_id.insert(champion.id, index);
+            let keys = [&champion.name, &champion.alias, &champion.localized_name]
+                .into_iter()
+                .chain(champion.aliases.iter());
+            for key in keys {
+                let key = normalize(key);
+                if key.is_empty() {
+                    continue;
+                }
+                let indices = self.by_name.entry(key).or_default();
+                if !indices.contains(&index) {
+                    indices.push(index);
+                }
+            }
+        }
+    }
+
+    /// Builds a catalog from the client's `champion-summary.json`. Names there are
+    /// in the client's language; English names and aliases come from `dataset`.
+    pub fn from_summary(version: String, locale: String, summary: &[serde_json::Value], dataset: &ChampionDataset) -> Self {
+        let champions = summary
+            .iter()
+            .filter_map(|entry| {
+                let id = entry.get("id").and_then(|id| id.as_i64())?;
+                // -1 is the "None" placeholder entry.
+                if id <= 0 {
+                    return None;
+                }
+
+                let localized_name = entry.get("name").and_then(|n| n.as_str()).unwrap_or_default().to_string();
+                let record = dataset.champions.iter().find(|c| c.id == id);
+                Some(ChampionInfo {
+                    id,
+                    alias: entry.get("alias").and_then(|a| a.as_str()).unwrap_or_default().to_string(),
+                    name: record.map(|r| r.name.clone()).unwrap_or_else(|| localized_name.clone()),
+                    aliases: record.map(|r| r.aliases.clone()).unwrap_or_default(),
+                    roles: entry
+                        .get("roles")
+                        .and_then(|r| r.as_array())
+                        .map(|roles| roles.iter().filter_map(|r| r.as_str()).map(|r| r.to_string()).collect())
+                        .unwrap_or_default(),
+                    localized_name,
+                })
+            })
+            .collect();
+
+        Self::new(version, locale, champions)
+    }
+
+    pub fn get(&self, id: i64) -> Option<&ChampionInfo> {
+        self.by_id.get(&id).map(|&index| &self.champions[index])
+    }
+
+    /// Resolves a user-typed champion name. Tries, in order: an exact match on
+    /// name, internal alias, localized name or nickname (ignoring case,
+    /// punctuation and spaces); a unique prefix; and finally the closest name
+    /// within a small edit distance. Callers that store the result should
+    /// check `kind`, since a non-exact match may not be what the user meant.
+    pub fn resolve(&self, query: &str) -> Result<ChampionMatch<'_>, ResolveError> {
+        let normalized = normalize(query);
+        if normalized.is_empty() {
+            return Err(ResolveError::NotFound { query: query.to_string(), suggestions: Vec::new() });
+        }
+
+        if let Some(indices) = self.by_name.get(&normalized) {
+            return self.single(query, indices.clone(), MatchKind::Exact);
+        }
+
+        if normalized.chars().count() >= 3 {
+            let mut prefix_matches: Vec<usize> = self.by_name
+                .iter()
+                .filter(|(key, _)| key.starts_with(&normalized))
+                .flat_map(|(_, indices)| indices.iter().copied())
+                .collect();
+            prefix_matches.sort_unstable();
+            prefix_matches.dedup();
+
+            if !prefix_matches.is_empty() {
+                return self.single(query, prefix_matches, MatchKind::Prefix);
+            }
+        }
+
+        let mut distances: Vec<(usize, usize)> = self.by_name
+            .iter()
+            .flat_map(|(key, indices)| {
+                let distance = edit_distance(&normalized, key);
+                indices.iter().map(move |&index| (distance, index))
+            })
+            .collect();
+        distances.sort_unstable();
+        let mut seen = HashSet::new();
+        distances.retain(|(_, index)| seen.insert(*index));
+
+        let max_distance = (normalized.chars().count() / 4).clamp(1, 2);
+        if let Some(&(best, _)) = distances.first() {
+            if best <= max_distance {
+                let mut closest: Vec<usize> = distances.iter()
+                    .filter(|(distance, _)| *distance == best)
+                    .map(|&(_, index)| index)
+                    .collect();
+                closest.sort_unstable();
+                closest.dedup();
+                return self.single(query, closest, MatchKind::Fuzzy);
+            }
+        }
+
+        let mut suggestions: Vec<String> = Vec::new();
+        for (_, index) in distances.iter().filter(|(distance, _)| *distance <= max_distance + 2) {
+            let name = &self.champions[*index].name;
+            if !suggestions.contains(name) {
+                suggestions.push(name.clone());
+            }
+            if suggestions.len() == 3 {
+                break;
+            }
+        }
+
+        Err(ResolveError::NotFound { query: query.to_string(), suggestions })
+    }
+
+    fn single(&self, query: &str, indices: Vec<usize>, kind: MatchKind) -> Result<ChampionMatch<'_>, ResolveError> {
+        match indices.as_slice() {
+            [index] => Ok(ChampionMatch { champion: &self.champions[*index], kind }),
+            _ => {
+                let mut candidates: Vec<String> = indices.iter().map(|&i| self.champions[i].name.clone()).collect();
+                candidates.sort();
+                candidates.dedup();
+                Err(ResolveError::Ambiguous { query: query.to_string(), candidates })
+            }
+        }
+    }
+
+    /// Champions sorted by display name, as listed in the settings.
+    pub fn summaries(&self) -> Vec<ChampionSummary> {
+        let mut summaries: Vec<ChampionSummary> = self.champions
+            .iter()
+            .map(|c| ChampionSummary { id: c.id, name: c.name.clone(), alias: c.alias.clone() })
+            .collect();
+        summaries.sort_by(|a, b| a.name.cmp(&b.name));
+        summaries
+    }
+
+    pub fn load_cached(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+        let mut catalog: Self = serde_json::from_str(&content).ok()?;
+        catalog.build_index();
+        Some(catalog)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), LeagueError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let content = serde_json::to_string(self)
+            .map_err(|e| format!("Could not serialize champion catalog: {}", e))?;
+        fs::write(path, content)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog() -> ChampionCatalog {
+        ChampionDataset::bundled().to_catalog()
+    }
+
+    #[test]
+    fn resolves_names_nicknames_and_punctuation() {
+        let catalog = catalog();
+        let cases = [
+            ("Kai'Sa", 145),
+            ("kaisa", 145),
+            ("KAI SA", 145),
+            ("Nunu & Willump", 20),
+            ("nunu and willump", 20),
+            ("Nunu", 20),
+            ("Jarvan IV", 59),
+            ("J4", 59),
+            ("MonkeyKing", 62),
+            ("mf", 21),
+        ];
+        for (query, id) in cases {
+            let found = catalog.resolve(query).unwrap_or_else(|e| panic!("{}: {}", query, e));
+            assert_eq!((found.champion.id, found.kind), (id, MatchKind::Exact), "{}", query);
+        }
+    }
+
+    #[test]
+    fn corrections_are_reported_as_such() {
+        let catalog = catalog();
+        let cases = [
+            ("Ash", 22, MatchKind::Prefix),
+            ("Jarvan I", 59, MatchKind::Prefix),
+            ("Yasou", 157, MatchKind::Fuzzy),
+            ("Jihx", 222, MatchKind::Fuzzy),
+            ("Dariusss", 122, MatchKind::Fuzzy),
+        ];
+        for (query, id, kind) in cases {
+            let found = catalog.resolve(query).unwrap_or_else(|e| panic!("{}: {}", query, e));
+            assert_eq!((found.champion.id, found.kind), (id, kind), "{}", query);
+        }
+    }
+
+    #[test]
+    fn ambiguous_prefixes_list_the_candidates() {
+        let catalog = catalog();
+        assert_eq!(
+            catalog.resolve("Kay").unwrap_err(),
+            ResolveError::Ambiguous { query: "Kay".to_string(), candidates: vec!["Kayle".to_string(), "Kayn".to_string()] }
+        );
+        assert!(matches!(catalog.resolve("Mal"), Err(ResolveError::Ambiguous { .. })));
+    }
+
+    #[test]
+    fn fuzzy_matches_stay_within_the_max_distance() {
+        let catalog = catalog();
+
+        // Nine letters allow two edits; this is three away from Darius.
+        match catalog.resolve("Dariussss") {
+            Err(ResolveError::NotFound { suggestions, .. }) => assert!(suggestions.contains(&"Darius".to_string())),
+            other => panic!("{:?}", other.map(|found| found.champion.name.clone())),
+        }
+        // Short queries allow a single edit.
+        assert!(catalog.resolve("Zqq").is_err());
+        assert_eq!(catalog.resolve("").unwrap_err(), ResolveError::NotFound { query: String::new(), suggestions: Vec::new() });
+    }
+
+    #[test]
+    fn edit_distance_counts_transpositions_once() {
+        assert_eq!(edit_distance("yasou", "yasuo"), 1);
+        assert_eq!(edit_distance("jinx", "jinx"), 0);
+        assert_eq!(edit_distance("zed", "zac"), 2);
+        assert_eq!(edit_distance("", "ahri"), 4);
+    }
+}
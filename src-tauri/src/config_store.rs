@@ -0,0 +1,241 @@
+use crate::champion_catalog::{self, ChampionCatalog, MatchKind};
+use crate::league_client::{ChampSelectConfig, LeagueError};
+use crate::logging;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use tracing::{info, warn};
+
+pub const CONFIG_FILE: &str = "config.json";
+
+/// A champion name from an old config that didn't match a champion exactly,
+/// so it was kept here instead of being converted to an ID.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UnresolvedChampion {
+    /// The setting it came from, e.g. `pick_priority`.
+    pub setting: String,
+    pub name: String,
+    pub reason: String,
+}
+
+/// Settings for the app as a whole rather than for champion select, saved
+/// under `app` in the config file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AppSettings {
+    /// error, warn, info, debug or trace.
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+}
+
+fn default_log_level() -> String {
+    logging::DEFAULT_LEVEL.to_string()
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self { log_level: default_log_level() }
+    }
+}
+
+/// JSON pointers of the settings that hold champion IDs.
+pub const CHAMPION_LISTS: [&str; 3] = ["/pick_priority", "/ban_priority", "/dodge_protection/pool"];
+
+/// Loads the saved configuration, falling back to defaults when there is none
+/// or it cannot be read.
+pub fn load(path: &Path) -> ChampSelectConfig {
+    if !path.exists() {
+        return ChampSelectConfig::default();
+    }
+
+    let parsed = fs::read_to_string(path).map(|content| {
+        let mut value: Value = serde_json::from_str(&content)?;
+        let migrated = migrate_champion_names(&mut value, &champion_catalog::fallback_catalog())
+            | migrate_app_settings(&mut value);
+        serde_json::from_value::<ChampSelectConfig>(value).map(|config| (config, migrated))
+    });
+
+    match parsed {
+        Ok(Ok((config, migrated))) => {
+            if migrated {
+                info!("Updated {} to the current config format", path.display());
+                let backup = path.with_extension("json.bak");
+                match fs::copy(path, &backup) {
+                    Ok(_) => {
+                        info!("Saved the previous config to {}", backup.display());
+                        if let Err(e) = save(path, &config) {
+                            warn!("Could not save migrated config: {}", e);
+                        }
+                    }
+                    Err(e) => warn!("Not saving migrated config, could not back up {}: {}", path.display(), e),
+                }
+            }
+            config
+        }
+        Ok(Err(e)) => {
+            warn!("Ignoring invalid config file {}: {}", path.display(), e);
+            ChampSelectConfig::default()
+        }
+        Err(e) => {
+            warn!("Could not read config file {}: {}", path.display(), e);
+            ChampSelectConfig::default()
+        }
+    }
+}
+
+pub fn save(path: &Path, config: &ChampSelectConfig) -> Result<(), LeagueError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let content = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Could not serialize config: {}", e))?;
+
+    // Write to a temporary file first so a crash mid-write can't corrupt the config.
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, content)?;
+    fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}
+
+/// Merges `changes` into `config`: objects are merged key by key, anything
+/// else replaces the current value.
+pub fn merge(config: &mut Value, changes: Value) {
+    match (config, changes) {
+        (Value::Object(current), Value::Object(changes)) => {
+            for (key, value) in changes {
+                match current.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        current.insert(key, value);
+                    }
+                }
+            }
+        }
+        (config, changes) => *config = changes,
+    }
+}
+
+/// Configs saved before the `app` section kept `log_level` at the top level.
+/// Moves it there. Returns whether anything changed.
+pub fn migrate_app_settings(config: &mut Value) -> bool {
+    let Some(object) = config.as_object_mut() else {
+        return false;
+    };
+    let Some(log_level) = object.remove("log_level") else {
+        return false;
+    };
+
+    let app = object.entry("app").or_insert_with(|| Value::Object(Default::default()));
+    if let Value::Object(app) = app {
+        app.entry("log_level").or_insert(log_level);
+    }
+    true
+}
+
+/// Configs saved before champion IDs were stored hold champion names in the
+/// priority lists and last-resort pool. Replaces each name that matches a
+/// champion exactly with its ID. Other names are moved to `legacy_unresolved`
+/// for the user to fix rather than guessed at. Returns whether anything changed.
+pub fn migrate_champion_names(config: &mut Value, catalog: &ChampionCatalog) -> bool {
+    let mut migrated = false;
+    let mut unresolved = Vec::new();
+
+    for pointer in CHAMPION_LISTS {
+        let Some(Value::Array(entries)) = config.pointer_mut(pointer) else {
+            continue;
+        };
+        if entries.iter().all(|entry| entry.is_i64()) {
+            continue;
+        }
+        migrated = true;
+
+        let setting = pointer[1..].replace('/', ".");
+        *entries = entries
+            .iter()
+            .filter_map(|entry| {
+                let Value::String(name) = entry else {
+                    return entry.is_i64().then(|| entry.clone());
+                };
+                let reason = match catalog.resolve(name) {
+                    Ok(found) if found.kind == MatchKind::Exact => return Some(Value::from(found.champion.id)),
+                    Ok(found) => format!("Not an exact champion name, closest match is {}", found.champion.name),
+                    Err(e) => e.to_string(),
+                };
+                warn!("Keeping '{}' from {} unconverted: {}", name, setting, reason);
+                unresolved.push(UnresolvedChampion { setting: setting.clone(), name: name.clone(), reason });
+                None
+            })
+            .collect();
+    }
+
+    if let (false, Some(object)) = (unresolved.is_empty(), config.as_object_mut()) {
+        let legacy = object.entry("legacy_unresolved").or_insert_with(|| Value::Array(Vec::new()));
+        if let Value::Array(legacy) = legacy {
+            legacy.extend(unresolved.iter().filter_map(|entry| serde_json::to_value(entry).ok()));
+        }
+    }
+
+    migrated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::champion_catalog::ChampionDataset;
+    use serde_json::json;
+
+    #[test]
+    fn migrates_only_exact_names() {
+        let catalog = ChampionDataset::bundled().to_catalog();
+        let mut config = json!({
+            "pick_priority": ["Jinx", 22, "Ash", "Jnxi", "Kai'Sa"],
+            "ban_priority": [157, 238],
+            "dodge_protection": { "pool": ["Qwertyuiop"] },
+        });
+
+        assert!(migrate_champion_names(&mut config, &catalog));
+        assert_eq!(config["pick_priority"], json!([222, 22, 145]));
+        assert_eq!(config["ban_priority"], json!([157, 238]));
+        assert_eq!(config["dodge_protection"]["pool"], json!([]));
+
+        let unresolved: Vec<UnresolvedChampion> = serde_json::from_value(config["legacy_unresolved"].clone()).unwrap();
+        let kept: Vec<(&str, &str)> = unresolved.iter().map(|entry| (entry.setting.as_str(), entry.name.as_str())).collect();
+        assert_eq!(kept, vec![("pick_priority", "Ash"), ("pick_priority", "Jnxi"), ("dodge_protection.pool", "Qwertyuiop")]);
+        assert_eq!(unresolved[0].reason, "Not an exact champion name, closest match is Ashe");
+    }
+
+    #[test]
+    fn numeric_configs_are_left_alone() {
+        let catalog = ChampionDataset::bundled().to_catalog();
+        let mut config = json!({ "pick_priority": [222], "ban_priority": [] });
+        assert!(!migrate_champion_names(&mut config, &catalog));
+        assert!(config.get("legacy_unresolved").is_none());
+    }
+
+    #[test]
+    fn moves_log_level_into_app_settings() {
+        let mut config = json!({ "log_level": "debug", "pick_priority": [222] });
+        assert!(migrate_app_settings(&mut config));
+        assert_eq!(config, json!({ "app": { "log_level": "debug" }, "pick_priority": [222] }));
+        assert!(!migrate_app_settings(&mut config));
+    }
+
+    #[test]
+    fn load_backs_up_the_old_config_before_migrating() {
+        let dir = std::env::temp_dir().join(format!("league-auto-accept-config-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(CONFIG_FILE);
+        let original = r#"{ "auto_pick_enabled": true, "auto_ban_enabled": false, "pick_priority": ["Jinx", "Jnxi"], "ban_priority": [] }"#;
+        fs::write(&path, original).unwrap();
+
+        let config = load(&path);
+        assert_eq!(config.pick_priority, vec![222]);
+        assert_eq!(config.legacy_unresolved.len(), 1);
+        assert_eq!(fs::read_to_string(dir.join("config.json.bak")).unwrap(), original);
+        assert_eq!(load(&path).legacy_unresolved, config.legacy_unresolved);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,253 @@
+use crate::champion_catalog::{ChampionCatalog, ChampionMatch, MatchKind, ResolveError};
+use crate::config_store::CHAMPION_LISTS;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use tracing::warn;
+
+/// A priority list entry as sent by the settings UI or found in an old config:
+/// either a champion ID or a typed name.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum ChampionEntry {
+    Id(i64),
+    Name(String),
+}
+
+impl ChampionEntry {
+    fn is_empty(&self) -> bool {
+        match self {
+            ChampionEntry::Id(id) => *id <= 0,
+            ChampionEntry::Name(name) => name.trim().is_empty(),
+        }
+    }
+
+    fn resolve<'a>(&self, catalog: &'a ChampionCatalog) -> Result<ChampionMatch<'a>, ResolveError> {
+        match self {
+            ChampionEntry::Id(id) => catalog
+                .get(*id)
+                .map(|champion| ChampionMatch { champion, kind: MatchKind::Exact })
+                .ok_or_else(|| ResolveError::NotFound { query: id.to_string(), suggestions: Vec::new() }),
+            ChampionEntry::Name(name) => catalog.resolve(name),
+        }
+    }
+}
+
+impl std::fmt::Display for ChampionEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ChampionEntry::Id(id) => write!(f, "{}", id),
+            ChampionEntry::Name(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryStatus {
+    Ok,
+    Unknown,
+    Ambiguous,
+    /// Same champion as an earlier entry; dropped when saving.
+    Duplicate,
+    /// Resolved, but not in `owned-champions-minimal`.
+    NotOwned,
+    /// Resolved by prefix or spelling correction rather than an exact name.
+    Corrected,
+}
+
+impl EntryStatus {
+    pub fn is_error(self) -> bool {
+        matches!(self, EntryStatus::Unknown | EntryStatus::Ambiguous)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EntryReport {
+    pub input: String,
+    pub champion_id: Option<i64>,
+    pub champion_name: Option<String>,
+    pub status: EntryStatus,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationReport {
+    pub saved: bool,
+    pub pick_priority: Vec<EntryReport>,
+    pub ban_priority: Vec<EntryReport>,
+}
+
+impl ValidationReport {
+    /// Validates both priority lists. Ownership only matters for picks.
+    pub fn new(pick_priority: &[ChampionEntry], ban_priority: &[ChampionEntry], catalog: &ChampionCatalog, owned: Option<&HashSet<i64>>) -> Self {
+        Self {
+            saved: false,
+            pick_priority: validate_list(pick_priority, catalog, owned),
+            ban_priority: validate_list(ban_priority, catalog, None),
+        }
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.pick_priority.iter().chain(&self.ban_priority).any(|entry| entry.status.is_error())
+    }
+}
+
+/// Resolves each non-empty entry against `catalog`. Ownership is only checked
+/// when `owned` is known (i.e. the client is connected).
+pub fn validate_list(entries: &[ChampionEntry], catalog: &ChampionCatalog, owned: Option<&HashSet<i64>>) -> Vec<EntryReport> {
+    let mut seen = HashSet::new();
+
+    entries
+        .iter()
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| match entry.resolve(catalog) {
+            Ok(ChampionMatch { champion, kind }) => {
+                let (status, message) = if !seen.insert(champion.id) {
+                    (EntryStatus::Duplicate, Some(format!("{} is already in the list", champion.name)))
+                } else if owned.is_some_and(|owned| !owned.contains(&champion.id)) {
+                    (EntryStatus::NotOwned, Some(format!("You don't own {}", champion.name)))
+                } else if kind != MatchKind::Exact {
+                    (EntryStatus::Corrected, Some(format!("Read as {}", champion.name)))
+                } else {
+                    (EntryStatus::Ok, None)
+                };
+
+                EntryReport {
+                    input: entry.to_string(),
+                    champion_id: Some(champion.id),
+                    champion_name: Some(champion.name.clone()),
+                    status,
+                    message,
+                }
+            }
+            Err(e) => EntryReport {
+                input: entry.to_string(),
+                champion_id: None,
+                champion_name: None,
+                status: match e {
+                    ResolveError::NotFound { .. } => EntryStatus::Unknown,
+                    ResolveError::Ambiguous { .. } => EntryStatus::Ambiguous,
+                },
+                message: Some(e.to_string()),
+            },
+        })
+        .collect()
+}
+
+/// The champion IDs to store: resolved entries, without duplicates.
+pub fn champion_ids(report: &[EntryReport]) -> Vec<i64> {
+    report
+        .iter()
+        .filter(|entry| entry.status != EntryStatus::Duplicate)
+        .filter_map(|entry| entry.champion_id)
+        .collect()
+}
+
+/// Resolves the champion lists of a config edited as JSON (CLI, control API)
+/// to IDs, so names work there as they do in the settings UI. Fails on
+/// unknown or ambiguous champions rather than dropping them.
+pub fn resolve_champion_lists(config: &mut Value, catalog: &ChampionCatalog) -> Result<(), String> {
+    for pointer in CHAMPION_LISTS {
+        let Some(list) = config.pointer_mut(pointer) else {
+            continue;
+        };
+        let setting = pointer[1..].replace('/', ".");
+
+        let entries: Vec<ChampionEntry> = serde_json::from_value(list.clone())
+            .map_err(|e| format!("Invalid champion list for {}: {}", setting, e))?;
+        let report = validate_list(&entries, catalog, None);
+
+        let errors: Vec<String> = report
+            .iter()
+            .filter(|entry| entry.status.is_error())
+            .filter_map(|entry| entry.message.clone())
+            .collect();
+        if !errors.is_empty() {
+            return Err(format!("{}: {}", setting, errors.join("; ")));
+        }
+        for entry in report.iter().filter(|entry| entry.status == EntryStatus::Corrected) {
+            warn!("{}: '{}' read as {}", setting, entry.input, entry.champion_name.as_deref().unwrap_or_default());
+        }
+
+        *list = Value::from(champion_ids(&report));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::champion_catalog::ChampionDataset;
+
+    fn name(name: &str) -> ChampionEntry {
+        ChampionEntry::Name(name.to_string())
+    }
+
+    fn statuses(report: &[EntryReport]) -> Vec<(String, EntryStatus)> {
+        report.iter().map(|entry| (entry.input.clone(), entry.status)).collect()
+    }
+
+    #[test]
+    fn reports_each_entry() {
+        let catalog = ChampionDataset::bundled().to_catalog();
+        let owned: HashSet<i64> = [222, 22].into();
+        let entries = [
+            ChampionEntry::Id(222),
+            name(""),
+            name("Kay"),
+            name("Qwertyuiop"),
+            name("jinx"),
+            name("Ash"),
+            name("Zed"),
+            ChampionEntry::Id(0),
+        ];
+
+        let report = validate_list(&entries, &catalog, Some(&owned));
+        assert_eq!(statuses(&report), vec![
+            ("222".to_string(), EntryStatus::Ok),
+            ("Kay".to_string(), EntryStatus::Ambiguous),
+            ("Qwertyuiop".to_string(), EntryStatus::Unknown),
+            ("jinx".to_string(), EntryStatus::Duplicate),
+            ("Ash".to_string(), EntryStatus::Corrected),
+            ("Zed".to_string(), EntryStatus::NotOwned),
+        ]);
+        assert_eq!(report[4].message.as_deref(), Some("Read as Ashe"));
+        assert_eq!(champion_ids(&report), vec![222, 22, 238]);
+    }
+
+    #[test]
+    fn ownership_is_unchecked_without_the_client() {
+        let catalog = ChampionDataset::bundled().to_catalog();
+        let report = validate_list(&[name("Zed")], &catalog, None);
+        assert_eq!(report[0].status, EntryStatus::Ok);
+    }
+
+    #[test]
+    fn only_unknown_and_ambiguous_entries_block_saving() {
+        let catalog = ChampionDataset::bundled().to_catalog();
+        let owned: HashSet<i64> = [222].into();
+
+        let warnings = ValidationReport::new(&[name("Jinx"), name("Jinx"), name("Ash")], &[name("Zed")], &catalog, Some(&owned));
+        assert!(!warnings.has_errors());
+        // Bans aren't checked for ownership.
+        assert_eq!(warnings.ban_priority[0].status, EntryStatus::Ok);
+
+        let unknown = ValidationReport::new(&[], &[name("Qwertyuiop")], &catalog, None);
+        assert!(unknown.has_errors());
+    }
+
+    #[test]
+    fn json_lists_resolve_to_ids_or_fail() {
+        let catalog = ChampionDataset::bundled().to_catalog();
+
+        let mut config = serde_json::json!({ "pick_priority": ["Jinx", 22], "dodge_protection": { "pool": ["Kai'Sa"] } });
+        resolve_champion_lists(&mut config, &catalog).unwrap();
+        assert_eq!(config, serde_json::json!({ "pick_priority": [222, 22], "dodge_protection": { "pool": [145] } }));
+
+        let mut config = serde_json::json!({ "ban_priority": ["Kay"] });
+        let error = resolve_champion_lists(&mut config, &catalog).unwrap_err();
+        assert!(error.starts_with("ban_priority: 'Kay' matches several champions"), "{}", error);
+    }
+}
@@ -0,0 +1,287 @@
+use crate::events::EventBus;
+use crate::league_client::LeagueError;
+use crate::secret::Secret;
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap, StatusCode, Uri};
+use axum::middleware::{self, Next};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
+use tracing::{info, warn};
+
+/// Opt-in HTTP API for Stream Deck buttons, AutoHotkey scripts and the like.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ControlApiConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// `127.0.0.1` keeps the API on this machine; `0.0.0.0` opens it to the LAN.
+    #[serde(default = "default_bind_address")]
+    pub bind_address: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// Sent as `Authorization: Bearer <token>` or `?token=<token>`.
+    /// Generated when the API is first enabled.
+    #[serde(default)]
+    pub token: String,
+}
+
+fn default_bind_address() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_port() -> u16 {
+    48_120
+}
+
+impl Default for ControlApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: default_bind_address(),
+            port: default_port(),
+            token: String::new(),
+        }
+    }
+}
+
+impl ControlApiConfig {
+    pub fn socket_addr(&self) -> Result<SocketAddr, String> {
+        format!("{}:{}", self.bind_address.trim(), self.port)
+            .parse()
+            .map_err(|_| format!("Invalid bind address '{}'", self.bind_address))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        self.socket_addr()?;
+        if self.enabled && self.token.len() < 16 {
+            return Err("The control API token must be at least 16 characters".to_string());
+        }
+        Ok(())
+    }
+}
+
+pub fn generate_token() -> String {
+    rand::rng()
+        .sample_iter(rand::distr::Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect()
+}
+
+/// The operations the API exposes; the same ones as the app's commands.
+#[derive(Debug)]
+pub enum ControlRequest {
+    Status,
+    Start,
+    Stop,
+    GetConfig,
+    /// A partial config, merged into the current one.
+    SetConfig(Value),
+    ManualAccept,
+}
+
+pub type ControlFuture<'a> = Pin<Box<dyn Future<Output = Result<Value, String>> + Send + 'a>>;
+
+/// Carries out API requests. Implemented by the desktop app on top of its commands.
+pub trait ControlHandler: Send + Sync + 'static {
+    fn handle(&self, request: ControlRequest) -> ControlFuture<'_>;
+}
+
+struct ApiState {
+    handler: Arc<dyn ControlHandler>,
+    events: EventBus,
+    token: Secret,
+}
+
+pub async fn bind(config: &ControlApiConfig) -> Result<TcpListener, LeagueError> {
+    config.validate()?;
+    let addr = config.socket_addr()?;
+    TcpListener::bind(addr)
+        .await
+        .map_err(|e| format!("Could not listen on {}: {}", addr, e).into())
+}
+
+/// Serves the API on `listener` until the task is aborted.
+pub async fn serve(listener: TcpListener, token: Secret, handler: Arc<dyn ControlHandler>, events: EventBus) {
+    let state = Arc::new(ApiState { handler, events, token });
+    let router = Router::new()
+        .route("/api/status", get(status))
+        .route("/api/start", post(start))
+        .route("/api/stop", post(stop))
+        .route("/api/config", get(get_config).put(set_config))
+        .route("/api/accept", post(accept))
+        .route("/api/events", get(event_stream))
+        .layer(middleware::from_fn_with_state(state.clone(), authorize))
+        .with_state(state);
+
+    if let Ok(addr) = listener.local_addr() {
+        info!(%addr, "Control API listening");
+    }
+    if let Err(e) = axum::serve(listener, router).await {
+        warn!("Control API stopped: {}", e);
+    }
+}
+
+/// The token from the `Authorization: Bearer` header, or the `token` query
+/// parameter for clients that cannot set headers (e.g. `EventSource`).
+fn request_token<'a>(headers: &'a HeaderMap, uri: &'a Uri) -> Option<&'a str> {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let query = || uri.query()?.split('&').find_map(|pair| pair.strip_prefix("token="));
+
+    bearer.or_else(query).map(str::trim)
+}
+
+/// Compares in constant time, so the token can't be guessed from response timings.
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given.bytes().zip(expected.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+async fn authorize(State(state): State<Arc<ApiState>>, request: Request, next: Next) -> Response {
+    match request_token(request.headers(), request.uri()) {
+        Some(token) if tokens_match(token, state.token.expose()) => next.run(request).await,
+        _ => error(StatusCode::UNAUTHORIZED, "Missing or invalid token"),
+    }
+}
+
+fn error(status: StatusCode, message: &str) -> Response {
+    (status, Json(json!({ "error": message }))).into_response()
+}
+
+async fn run(state: &ApiState, request: ControlRequest) -> Response {
+    match state.handler.handle(request).await {
+        Ok(value) => Json(value).into_response(),
+        Err(message) => error(StatusCode::BAD_REQUEST, &message),
+    }
+}
+
+async fn status(State(state): State<Arc<ApiState>>) -> Response {
+    run(&state, ControlRequest::Status).await
+}
+
+async fn start(State(state): State<Arc<ApiState>>) -> Response {
+    run(&state, ControlRequest::Start).await
+}
+
+async fn stop(State(state): State<Arc<ApiState>>) -> Response {
+    run(&state, ControlRequest::Stop).await
+}
+
+async fn get_config(State(state): State<Arc<ApiState>>) -> Response {
+    run(&state, ControlRequest::GetConfig).await
+}
+
+async fn set_config(State(state): State<Arc<ApiState>>, Json(changes): Json<Value>) -> Response {
+    run(&state, ControlRequest::SetConfig(changes)).await
+}
+
+async fn accept(State(state): State<Arc<ApiState>>) -> Response {
+    run(&state, ControlRequest::ManualAccept).await
+}
+
+/// Server-Sent Events: one `event:` per service event, with the event as JSON data.
+async fn event_stream(State(state): State<Arc<ApiState>>) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let events = BroadcastStream::new(state.events.subscribe()).filter_map(|received| {
+        // Lagged receivers skip what they missed and carry on.
+        let event = received.ok()?;
+        Event::default().event(&event.event).json_data(&event).ok().map(Ok)
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn token_from_header_or_query() {
+        let uri: Uri = "/api/events?foo=1&token=abc123".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        assert_eq!(request_token(&headers, &uri), Some("abc123"));
+
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer from-header"));
+        assert_eq!(request_token(&headers, &uri), Some("from-header"));
+
+        let bare: Uri = "/api/status".parse().unwrap();
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Basic dXNlcjpwYXNz"));
+        assert_eq!(request_token(&headers, &bare), None);
+    }
+
+    #[test]
+    fn tokens_compare_exactly() {
+        assert!(tokens_match("s3cret-token-value", "s3cret-token-value"));
+        assert!(!tokens_match("s3cret-token-valuE", "s3cret-token-value"));
+        assert!(!tokens_match("s3cret", "s3cret-token-value"));
+        assert!(!tokens_match("", "s3cret-token-value"));
+    }
+
+    #[test]
+    fn enabled_api_needs_a_token_and_a_valid_address() {
+        let mut config = ControlApiConfig { enabled: true, ..ControlApiConfig::default() };
+        assert!(config.validate().is_err());
+
+        config.token = generate_token();
+        assert!(config.validate().is_ok());
+
+        config.bind_address = "not an address".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    struct Echo;
+
+    impl ControlHandler for Echo {
+        fn handle(&self, request: ControlRequest) -> ControlFuture<'_> {
+            Box::pin(async move {
+                match request {
+                    ControlRequest::SetConfig(changes) => Ok(changes),
+                    ControlRequest::Start => Err("Service is already running".to_string()),
+                    other => Ok(json!({ "request": format!("{:?}", other) })),
+                }
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn requests_need_the_token_and_reach_the_handler() {
+        let token = generate_token();
+        let config = ControlApiConfig { enabled: true, port: 0, token: token.clone(), ..ControlApiConfig::default() };
+        let listener = bind(&config).await.unwrap();
+        let base = format!("http://{}/api", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve(listener, Secret::new(token.clone()), Arc::new(Echo), EventBus::default()));
+        let client = reqwest::Client::new();
+
+        let response = client.get(format!("{}/status", base)).send().await.unwrap();
+        assert_eq!(response.status(), 401);
+
+        let response = client.get(format!("{}/status", base)).bearer_auth(&token).send().await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.json::<Value>().await.unwrap(), json!({ "request": "Status" }));
+
+        let response = client.post(format!("{}/start?token={}", base, token)).send().await.unwrap();
+        assert_eq!(response.status(), 400);
+        assert_eq!(response.json::<Value>().await.unwrap(), json!({ "error": "Service is already running" }));
+
+        let changes = json!({ "ready_check": { "accept_delay_secs": 3 } });
+        let response = client.put(format!("{}/config", base)).bearer_auth(&token).json(&changes).send().await.unwrap();
+        assert_eq!(response.json::<Value>().await.unwrap(), changes);
+
+        server.abort();
+    }
+}
@@ -0,0 +1,231 @@
+use crate::champion_catalog::{ChampionCatalog, MatchKind};
+use crate::league_client::LeagueError;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tracing::warn;
+
+pub const COUNTER_PICKS_FILE: &str = "counter_picks.json";
+
+/// One line of the user-maintained counter-pick table:
+/// "if the enemy has `enemy` (in `lane`), prefer `prefer` in that order".
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CounterPickRule {
+    pub enemy: String,
+    #[serde(default)]
+    pub lane: Option<String>,
+    pub prefer: Vec<String>,
+}
+
+/// A champion the enemy team has locked in, as seen from champion select.
+#[derive(Debug, Clone)]
+pub struct EnemyPick {
+    pub champion_id: i64,
+    pub position: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CounterPickTable {
+    pub rules: Vec<CounterPickRule>,
+}
+
+impl CounterPickTable {
+    /// Loads the table from `path`. A missing file is an empty table, not an error.
+    pub fn load(path: &Path) -> Result<Self, LeagueError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        let rules: Vec<CounterPickRule> = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid counter-pick file {}: {}", path.display(), e))?;
+
+        Ok(Self { rules })
+    }
+
+    /// Returns the first rule whose enemy champion is locked in our lane.
+    ///
+    /// `resolve` maps a champion name from the table to its ID. A rule with a
+    /// `lane` only applies when that is our assigned position; enemy positions
+    /// are compared only when the client exposes them (blind draft hides them).
+    pub fn find_rule<F>(&self, my_position: Option<&str>, enemies: &[EnemyPick], mut resolve: F) -> Option<&CounterPickRule>
+    where
+        F: FnMut(&str) -> Option<i64>,
+    {
+        let my_position = my_position.filter(|p| !p.is_empty());
+
+        self.rules.iter().find(|rule| {
+            if let Some(lane) = rule.lane.as_deref() {
+                match my_position {
+                    Some(position) if position.eq_ignore_ascii_case(lane) => {}
+                    _ => return false,
+                }
+            }
+
+            let Some(enemy_id) = resolve(&rule.enemy) else {
+                return false;
+            };
+
+            enemies.iter().any(|enemy| {
+                if enemy.champion_id != enemy_id {
+                    return false;
+                }
+                match (my_position, enemy.position.as_deref().filter(|p| !p.is_empty())) {
+                    (Some(mine), Some(theirs)) => mine.eq_ignore_ascii_case(theirs),
+                    _ => true,
+                }
+            })
+        })
+    }
+}
+
+/// The ID of the champion `name` names exactly. A rule is written by hand,
+/// so a prefix or misspelling is skipped with a warning rather than read as
+/// whichever champion it resembles.
+pub fn resolve_exact(catalog: &ChampionCatalog, name: &str) -> Option<i64> {
+    match catalog.resolve(name) {
+        Ok(found) if found.kind == MatchKind::Exact => Some(found.champion.id),
+        Ok(found) => {
+            warn!("Skipping '{}' in the counter-pick table: not an exact champion name, closest match is {}", name, found.champion.name);
+            None
+        }
+        Err(e) => {
+            warn!("Skipping '{}' in the counter-pick table: {}", name, e);
+            None
+        }
+    }
+}
+
+/// The table in `path`, read again only when the file's modification time
+/// changes. A missing, unreadable or invalid file is an empty table.
+#[derive(Debug)]
+pub struct CounterPickCache {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    table: CounterPickTable,
+}
+
+impl CounterPickCache {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, modified: None, table: CounterPickTable::default() }
+    }
+
+    pub fn table(&mut self) -> &CounterPickTable {
+        let modified = fs::metadata(&self.path).and_then(|metadata| metadata.modified()).ok();
+        if modified != self.modified {
+            self.table = CounterPickTable::load(&self.path).unwrap_or_else(|e| {
+                warn!("Ignoring counter-pick table: {}", e);
+                CounterPickTable::default()
+            });
+            self.modified = modified;
+        }
+        &self.table
+    }
+}
+
+/// Moves the rule's preferred champions (resolved to IDs) to the front of
+/// `pick_priority`, keeping the rest of the list in its original order.
+pub fn reorder_priority(pick_priority: &[i64], prefer: &[i64]) -> Vec<i64> {
+    let mut ordered: Vec<i64> = Vec::with_capacity(pick_priority.len() + prefer.len());
+
+    for &champion_id in prefer.iter().chain(pick_priority.iter()) {
+        if !ordered.contains(&champion_id) {
+            ordered.push(champion_id);
+        }
+    }
+
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const YASUO: i64 = 157;
+    const DARIUS: i64 = 122;
+
+    fn rule(enemy: &str, lane: Option<&str>, prefer: &[&str]) -> CounterPickRule {
+        CounterPickRule {
+            enemy: enemy.to_string(),
+            lane: lane.map(String::from),
+            prefer: prefer.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    fn resolve(name: &str) -> Option<i64> {
+        match name {
+            "Yasuo" => Some(YASUO),
+            "Darius" => Some(DARIUS),
+            _ => None,
+        }
+    }
+
+    fn enemy(champion_id: i64, position: Option<&str>) -> EnemyPick {
+        EnemyPick { champion_id, position: position.map(String::from) }
+    }
+
+    fn table() -> CounterPickTable {
+        CounterPickTable {
+            rules: vec![
+                rule("Yasuo", Some("middle"), &["Annie"]),
+                rule("Darius", None, &["Vayne"]),
+                rule("Nobody", None, &["Teemo"]),
+            ],
+        }
+    }
+
+    #[test]
+    fn lane_rules_need_our_position_to_match() {
+        let table = table();
+        let enemies = [enemy(YASUO, None)];
+
+        let found = table.find_rule(Some("MIDDLE"), &enemies, resolve);
+        assert_eq!(found.map(|rule| rule.enemy.as_str()), Some("Yasuo"));
+        assert!(table.find_rule(Some("top"), &enemies, resolve).is_none());
+        assert!(table.find_rule(None, &enemies, resolve).is_none());
+    }
+
+    #[test]
+    fn rules_without_a_lane_compare_enemy_positions_when_known() {
+        let table = table();
+
+        let blind = [enemy(DARIUS, None)];
+        assert_eq!(table.find_rule(None, &blind, resolve).map(|rule| rule.enemy.as_str()), Some("Darius"));
+
+        let other_lane = [enemy(DARIUS, Some("top"))];
+        assert!(table.find_rule(Some("bottom"), &other_lane, resolve).is_none());
+        assert!(table.find_rule(Some("top"), &other_lane, resolve).is_some());
+    }
+
+    #[test]
+    fn unresolved_enemies_never_match() {
+        assert!(table().find_rule(None, &[enemy(1, None)], resolve).is_none());
+    }
+
+    #[test]
+    fn preferred_champions_move_to_the_front() {
+        assert_eq!(reorder_priority(&[222, 22, 51], &[51, 145]), vec![51, 145, 222, 22]);
+        assert_eq!(reorder_priority(&[222, 22], &[]), vec![222, 22]);
+    }
+
+    #[test]
+    fn cache_reloads_when_the_file_changes() {
+        let path = std::env::temp_dir().join(format!("league-auto-accept-counter-picks-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut cache = CounterPickCache::new(path.clone());
+        assert!(cache.table().rules.is_empty());
+
+        fs::write(&path, r#"[{ "enemy": "Yasuo", "prefer": ["Annie"] }]"#).unwrap();
+        assert_eq!(cache.table().rules.len(), 1);
+
+        fs::write(&path, r#"[{ "enemy": "Yasuo", "prefer": ["Annie"] }, { "enemy": "Zed", "prefer": ["Lissandra"] }]"#).unwrap();
+        let later = SystemTime::now() + Duration::from_secs(5);
+        fs::File::options().write(true).open(&path).unwrap().set_modified(later).unwrap();
+        assert_eq!(cache.table().rules.len(), 2);
+
+        fs::remove_file(&path).unwrap();
+        assert!(cache.table().rules.is_empty());
+    }
+}
@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DodgeProtectionConfig {
+    /// Lock in any available champion when the pick timer is about to run out.
+    #[serde(default)]
+    pub last_resort_enabled: bool,
+    #[serde(default = "default_threshold_ms")]
+    pub last_resort_threshold_ms: i64,
+    /// Champion IDs to restrict the last-resort pick to, in order. Empty means any owned champion.
+    #[serde(default)]
+    pub pool: Vec<i64>,
+}
+
+fn default_threshold_ms() -> i64 {
+    5000
+}
+
+impl Default for DodgeProtectionConfig {
+    fn default() -> Self {
+        Self {
+            last_resort_enabled: false,
+            last_resort_threshold_ms: default_threshold_ms(),
+            pool: Vec::new(),
+        }
+    }
+}
+
+impl DodgeProtectionConfig {
+    pub fn should_force_pick(&self, time_left_ms: i64) -> bool {
+        self.last_resort_enabled && time_left_ms > 0 && time_left_ms <= self.last_resort_threshold_ms
+    }
+}
+
+/// Orders the champions for a last-resort pick: the hovered champion first, then
+/// the pool (or every pickable champion when the pool is empty), keeping only
+/// champions the client says are pickable.
+pub fn last_resort_candidates(hovered: Option<i64>, pool: &[i64], pickable: &[i64]) -> Vec<i64> {
+    let preferred: Vec<i64> = if pool.is_empty() { pickable.to_vec() } else { pool.to_vec() };
+
+    let mut candidates = Vec::new();
+    for id in hovered.into_iter().chain(preferred) {
+        if id > 0 && pickable.contains(&id) && !candidates.contains(&id) {
+            candidates.push(id);
+        }
+    }
+    candidates
+}
+
+/// What we saw of the current champion select, kept until it ends.
+#[derive(Debug, Clone, Default)]
+pub struct ChampSelectTracker {
+    pub locked_in: bool,
+    pub has_pick_action: bool,
+    pub queue_id: Option<i64>,
+    pub timer_phase: String,
+}
+
+impl ChampSelectTracker {
+    /// Champion select is over; decide from the gameflow phase whether the game
+    /// actually started. Returns the dodge reason if it did not.
+    pub fn dodge_reason(&self, gameflow_phase: &str) -> Option<String> {
+        match gameflow_phase {
+            "ChampSelect" | "GameStart" | "InProgress" | "Reconnect" | "WaitingForStats" | "PreEndOfGame" | "EndOfGame" => None,
+            _ if self.has_pick_action && !self.locked_in => {
+                Some(format!("We did not lock in a champion before champion select ended ({})", self.timer_phase))
+            }
+            _ => Some(format!("Another player dodged ({})", self.timer_phase)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DodgeRecord {
+    pub timestamp: String,
+    pub reason: String,
+    pub queue_id: Option<i64>,
+    pub locked_in: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker(has_pick_action: bool, locked_in: bool) -> ChampSelectTracker {
+        ChampSelectTracker { locked_in, has_pick_action, queue_id: Some(420), timer_phase: "FINALIZATION".to_string() }
+    }
+
+    #[test]
+    fn no_dodge_when_the_game_starts() {
+        for phase in ["GameStart", "InProgress", "ChampSelect"] {
+            assert_eq!(tracker(true, false).dodge_reason(phase), None, "{}", phase);
+        }
+    }
+
+    #[test]
+    fn blames_us_only_when_we_did_not_lock_in() {
+        assert_eq!(
+            tracker(true, false).dodge_reason("Lobby").as_deref(),
+            Some("We did not lock in a champion before champion select ended (FINALIZATION)")
+        );
+        assert_eq!(tracker(true, true).dodge_reason("Matchmaking").as_deref(), Some("Another player dodged (FINALIZATION)"));
+        assert_eq!(tracker(false, false).dodge_reason("Lobby").as_deref(), Some("Another player dodged (FINALIZATION)"));
+    }
+
+    #[test]
+    fn last_resort_prefers_the_hovered_champion_then_the_pool() {
+        let pickable = [1, 2, 3, 4];
+        assert_eq!(last_resort_candidates(Some(3), &[4, 9, 3, 1], &pickable), vec![3, 4, 1]);
+        assert_eq!(last_resort_candidates(None, &[], &pickable), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn last_resort_skips_unpickable_and_empty_hovers() {
+        assert_eq!(last_resort_candidates(Some(0), &[2], &[1, 2]), vec![2]);
+        assert_eq!(last_resort_candidates(Some(5), &[], &[1]), vec![1]);
+        assert!(last_resort_candidates(None, &[7], &[1]).is_empty());
+    }
+
+    #[test]
+    fn forces_a_pick_only_inside_the_threshold() {
+        let config = DodgeProtectionConfig { last_resort_enabled: true, ..DodgeProtectionConfig::default() };
+        assert!(config.should_force_pick(5000));
+        assert!(!config.should_force_pick(5001));
+        assert!(!config.should_force_pick(0));
+        assert!(!DodgeProtectionConfig::default().should_force_pick(1000));
+    }
+}
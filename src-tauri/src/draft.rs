@@ -0,0 +1,420 @@
+use crate::champ_select::{ActionType, ChampSelectSession};
+use crate::champion_catalog::ChampionCatalog;
+use crate::counter_picks::{self, CounterPickRule, CounterPickTable};
+use crate::dodge;
+use crate::league_client::ChampSelectConfig;
+use std::collections::HashSet;
+
+/// A champion to try, in order, for a planned ban or pick.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub champion_id: i64,
+    pub name: String,
+    /// 1-based position in the configured priority list; `None` for
+    /// counter-pick additions and last-resort fallbacks.
+    pub slot: Option<usize>,
+}
+
+/// What to do for one of our champion select actions. The executor tries the
+/// candidates in order until the client accepts one; an empty list means
+/// nothing from the configured list is available.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlannedAction {
+    Ban {
+        action_id: i64,
+        candidates: Vec<Candidate>,
+    },
+    Pick {
+        action_id: i64,
+        candidates: Vec<Candidate>,
+        counter_rule: Option<CounterPickRule>,
+    },
+    /// The pick timer is about to run out: lock in immediately.
+    LastResortPick {
+        action_id: i64,
+        candidates: Vec<Candidate>,
+    },
+}
+
+/// Decides what to ban or pick for the actions that are currently ours.
+///
+/// `owned` holds the champions we can pick (the client's pickable list, or
+/// owned champions when that is unavailable). Champions that are banned or
+/// locked in by another player are never planned.
+pub fn decide(
+    session: &ChampSelectSession,
+    config: &ChampSelectConfig,
+    catalog: &ChampionCatalog,
+    owned: &HashSet<i64>,
+    counter_picks: &CounterPickTable,
+) -> Vec<PlannedAction> {
+    let unavailable = unavailable_champions(session);
+    let candidate = |champion_id: i64, slot: Option<usize>| Candidate {
+        champion_id,
+        name: catalog.get(champion_id).map(|c| c.name.clone()).unwrap_or_else(|| format!("champion #{}", champion_id)),
+        slot,
+    };
+    let config_slot = |list: &[i64], champion_id: i64| list.iter().position(|&id| id == champion_id).map(|index| index + 1);
+
+    let mut plan = Vec::new();
+    for action in session.my_actions().filter(|action| action.is_actionable()) {
+        match action.action_type {
+            ActionType::Pick if config.dodge_protection.should_force_pick(session.timer.time_left_ms()) => {
+                let mut pickable: Vec<i64> = owned.iter().copied().filter(|id| !unavailable.contains(id)).collect();
+                pickable.sort_unstable();
+                let hovered = (action.champion_id > 0).then_some(action.champion_id);
+
+                plan.push(PlannedAction::LastResortPick {
+                    action_id: action.id,
+                    candidates: dodge::last_resort_candidates(hovered, &config.dodge_protection.pool, &pickable)
+                        .into_iter()
+                        .map(|champion_id| candidate(champion_id, None))
+                        .collect(),
+                });
+            }
+            // Something is already hovered; leave it to the player.
+            _ if action.champion_id != 0 => {}
+            ActionType::Ban if config.auto_ban_enabled => {
+                plan.push(PlannedAction::Ban {
+                    action_id: action.id,
+                    candidates: config.ban_priority
+                        .iter()
+                        .copied()
+                        .filter(|id| !unavailable.contains(id))
+                        .map(|champion_id| candidate(champion_id, config_slot(&config.ban_priority, champion_id)))
+                        .collect(),
+                });
+            }
+            ActionType::Pick if config.auto_pick_enabled => {
+                let (priority, counter_rule) = counter_pick_priority(session, config, catalog, counter_picks);
+
+                plan.push(PlannedAction::Pick {
+                    action_id: action.id,
+                    candidates: priority
+                        .into_iter()
+                        .filter(|id| owned.contains(id) && !unavailable.contains(id))
+                        .map(|champion_id| candidate(champion_id, config_slot(&config.pick_priority, champion_id)))
+                        .collect(),
+                    counter_rule,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    plan
+}
+
+/// Banned champions and champions another player has locked in.
+fn unavailable_champions(session: &ChampSelectSession) -> HashSet<i64> {
+    let locked = session.actions
+        .iter()
+        .flatten()
+        .filter(|action| action.completed && action.champion_id > 0)
+        .filter(|action| action.action_type == ActionType::Ban || action.actor_cell_id != session.local_player_cell_id)
+        .map(|action| action.champion_id);
+
+    session.bans.my_team_bans
+        .iter()
+        .chain(&session.bans.their_team_bans)
+        .copied()
+        .chain(locked)
+        .filter(|&id| id > 0)
+        .collect()
+}
+
+/// The pick priority, reordered by the first counter-pick rule whose enemy
+/// is in the session.
+fn counter_pick_priority(
+    session: &ChampSelectSession,
+    config: &ChampSelectConfig,
+    catalog: &ChampionCatalog,
+    counter_picks: &CounterPickTable,
+) -> (Vec<i64>, Option<CounterPickRule>) {
+    let enemies = session.enemy_picks();
+    if counter_picks.rules.is_empty() || enemies.is_empty() {
+        return (config.pick_priority.clone(), None);
+    }
+
+    let resolve = |name: &str| counter_picks::resolve_exact(catalog, name);
+    match counter_picks.find_rule(session.my_position(), &enemies, resolve) {
+        Some(rule) => {
+            let prefer: Vec<i64> = rule.prefer.iter().filter_map(|name| resolve(name)).collect();
+            (counter_picks::reorder_priority(&config.pick_priority, &prefer), Some(rule.clone()))
+        }
+        None => (config.pick_priority.clone(), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::champ_select::{Bans, ChampSelectAction, TeamMember, Timer};
+    use crate::champion_catalog::ChampionDataset;
+
+    const ME: i64 = 2;
+
+    const JINX: i64 = 222;
+    const ASHE: i64 = 22;
+    const CAITLYN: i64 = 51;
+    const YASUO: i64 = 157;
+    const ZED: i64 = 238;
+    const MASTER_YI: i64 = 11;
+    const DARIUS: i64 = 122;
+    const VAYNE: i64 = 67;
+    const ANNIE: i64 = 1;
+
+    fn action(id: i64, actor_cell_id: i64, action_type: ActionType) -> ChampSelectAction {
+        ChampSelectAction {
+            id,
+            actor_cell_id,
+            action_type,
+            is_ally_action: actor_cell_id < 5,
+            ..ChampSelectAction::default()
+        }
+    }
+
+    fn in_progress(mut action: ChampSelectAction, champion_id: i64) -> ChampSelectAction {
+        action.is_in_progress = true;
+        action.champion_id = champion_id;
+        action
+    }
+
+    fn completed(mut action: ChampSelectAction, champion_id: i64) -> ChampSelectAction {
+        action.completed = true;
+        action.champion_id = champion_id;
+        action
+    }
+
+    fn session(actions: Vec<Vec<ChampSelectAction>>) -> ChampSelectSession {
+        ChampSelectSession {
+            game_id: 1,
+            local_player_cell_id: ME,
+            actions,
+            my_team: (0..5)
+                .map(|cell_id| TeamMember {
+                    cell_id,
+                    assigned_position: ["top", "jungle", "middle", "bottom", "utility"][cell_id as usize].to_string(),
+                    ..TeamMember::default()
+                })
+                .collect(),
+            their_team: (5..10).map(|cell_id| TeamMember { cell_id, ..TeamMember::default() }).collect(),
+            timer: Timer {
+                phase: "BAN_PICK".to_string(),
+                adjusted_time_left_in_phase: Some(25_000),
+                ..Timer::default()
+            },
+            ..ChampSelectSession::default()
+        }
+    }
+
+    fn edited(mut session: ChampSelectSession, edit: impl FnOnce(&mut ChampSelectSession)) -> ChampSelectSession {
+        edit(&mut session);
+        session
+    }
+
+    fn config() -> ChampSelectConfig {
+        ChampSelectConfig {
+            auto_pick_enabled: true,
+            auto_ban_enabled: true,
+            pick_priority: vec![JINX, ASHE, CAITLYN],
+            ban_priority: vec![YASUO, ZED, MASTER_YI],
+            ..ChampSelectConfig::default()
+        }
+    }
+
+    fn owned_all() -> HashSet<i64> {
+        [JINX, ASHE, CAITLYN, VAYNE, ANNIE].into_iter().collect()
+    }
+
+    /// `(kind, action_id, [(champion_id, slot)])`, to keep expectations short.
+    type Planned = (&'static str, i64, Vec<(i64, Option<usize>)>);
+
+    fn summary(plan: &[PlannedAction]) -> Vec<Planned> {
+        plan.iter()
+            .map(|planned| {
+                let (kind, action_id, candidates) = match planned {
+                    PlannedAction::Ban { action_id, candidates } => ("ban", *action_id, candidates),
+                    PlannedAction::Pick { action_id, candidates, .. } => ("pick", *action_id, candidates),
+                    PlannedAction::LastResortPick { action_id, candidates } => ("last_resort", *action_id, candidates),
+                };
+                (kind, action_id, candidates.iter().map(|c| (c.champion_id, c.slot)).collect())
+            })
+            .collect()
+    }
+
+    struct Case {
+        name: &'static str,
+        session: ChampSelectSession,
+        config: ChampSelectConfig,
+        owned: HashSet<i64>,
+        counter_picks: Vec<CounterPickRule>,
+        expected: Vec<Planned>,
+    }
+
+    fn case(name: &'static str, session: ChampSelectSession, expected: Vec<Planned>) -> Case {
+        Case { name, session, config: config(), owned: owned_all(), counter_picks: Vec::new(), expected }
+    }
+
+    fn cases() -> Vec<Case> {
+        let ban_turn = || vec![
+            (0..5).map(|cell| {
+                let ban = action(cell + 1, cell, ActionType::Ban);
+                if cell == ME { in_progress(ban, 0) } else { ban }
+            }).collect(),
+            vec![action(20, ME, ActionType::Pick)],
+        ];
+        let pick_turn = |hovered: i64| vec![
+            vec![completed(action(3, ME, ActionType::Ban), YASUO)],
+            vec![in_progress(action(20, ME, ActionType::Pick), hovered)],
+        ];
+
+        vec![
+            case("not our turn", session(vec![vec![in_progress(action(1, 0, ActionType::Ban), 0)]]), vec![]),
+            case("spectating", ChampSelectSession { local_player_cell_id: -1, ..session(ban_turn()) }, vec![]),
+            case(
+                "ban turn uses the ban priority",
+                session(ban_turn()),
+                vec![("ban", 3, vec![(YASUO, Some(1)), (ZED, Some(2)), (MASTER_YI, Some(3))])],
+            ),
+            case(
+                "ban turn skips champions already banned",
+                edited(session(ban_turn()), |s| s.bans = Bans { their_team_bans: vec![YASUO], ..Bans::default() }),
+                vec![("ban", 3, vec![(ZED, Some(2)), (MASTER_YI, Some(3))])],
+            ),
+            Case {
+                config: ChampSelectConfig { auto_ban_enabled: false, ..config() },
+                ..case("auto-ban disabled", session(ban_turn()), vec![])
+            },
+            case(
+                "ban already hovered is left alone",
+                session(vec![vec![in_progress(action(3, ME, ActionType::Ban), ZED)]]),
+                vec![],
+            ),
+            case(
+                "pick turn uses the pick priority",
+                session(pick_turn(0)),
+                vec![("pick", 20, vec![(JINX, Some(1)), (ASHE, Some(2)), (CAITLYN, Some(3))])],
+            ),
+            Case {
+                owned: [ASHE, CAITLYN].into_iter().collect(),
+                ..case("pick skips champions we can't pick", session(pick_turn(0)), vec![("pick", 20, vec![(ASHE, Some(2)), (CAITLYN, Some(3))])])
+            },
+            case(
+                "pick skips champions locked by others",
+                edited(session(pick_turn(0)), |s| s.actions.push(vec![completed(action(30, 6, ActionType::Pick), JINX)])),
+                vec![("pick", 20, vec![(ASHE, Some(2)), (CAITLYN, Some(3))])],
+            ),
+            Case {
+                owned: HashSet::new(),
+                ..case("pick with nothing available plans an empty pick", session(pick_turn(0)), vec![("pick", 20, vec![])])
+            },
+            Case {
+                config: ChampSelectConfig { auto_pick_enabled: false, ..config() },
+                ..case("auto-pick disabled", session(pick_turn(0)), vec![])
+            },
+            case("pick already hovered is left alone", session(pick_turn(CAITLYN)), vec![]),
+            case(
+                "completed pick is ignored",
+                session(vec![vec![completed(action(20, ME, ActionType::Pick), JINX)]]),
+                vec![],
+            ),
+            Case {
+                counter_picks: vec![CounterPickRule { enemy: "Darius".to_string(), lane: None, prefer: vec!["Vayne".to_string(), "Caitlyn".to_string()] }],
+                ..case(
+                    "counter-pick rule moves preferred champions first",
+                    edited(session(pick_turn(0)), |s| s.their_team[0].champion_id = DARIUS),
+                    vec![("pick", 20, vec![(VAYNE, None), (CAITLYN, Some(3)), (JINX, Some(1)), (ASHE, Some(2))])],
+                )
+            },
+            Case {
+                counter_picks: vec![CounterPickRule { enemy: "Darius".to_string(), lane: None, prefer: vec!["Vaynee".to_string(), "Caitlyn".to_string()] }],
+                ..case(
+                    "counter-pick rule ignores misspelled preferred champions",
+                    edited(session(pick_turn(0)), |s| s.their_team[0].champion_id = DARIUS),
+                    vec![("pick", 20, vec![(CAITLYN, Some(3)), (JINX, Some(1)), (ASHE, Some(2))])],
+                )
+            },
+            Case {
+                counter_picks: vec![CounterPickRule { enemy: "Dari".to_string(), lane: None, prefer: vec!["Vayne".to_string()] }],
+                ..case(
+                    "counter-pick rule with a partial enemy name is ignored",
+                    edited(session(pick_turn(0)), |s| s.their_team[0].champion_id = DARIUS),
+                    vec![("pick", 20, vec![(JINX, Some(1)), (ASHE, Some(2)), (CAITLYN, Some(3))])],
+                )
+            },
+            Case {
+                counter_picks: vec![CounterPickRule { enemy: "Darius".to_string(), lane: Some("top".to_string()), prefer: vec!["Vayne".to_string()] }],
+                ..case(
+                    "counter-pick rule for another lane is ignored",
+                    edited(session(pick_turn(0)), |s| s.their_team[0].champion_id = DARIUS),
+                    vec![("pick", 20, vec![(JINX, Some(1)), (ASHE, Some(2)), (CAITLYN, Some(3))])],
+                )
+            },
+            Case {
+                config: ChampSelectConfig {
+                    dodge_protection: dodge::DodgeProtectionConfig { last_resort_enabled: true, last_resort_threshold_ms: 5000, pool: vec![ANNIE, ASHE] },
+                    ..config()
+                },
+                ..case(
+                    "last resort locks the hovered champion first, then the pool",
+                    edited(session(pick_turn(CAITLYN)), |s| s.timer.adjusted_time_left_in_phase = Some(3000)),
+                    vec![("last_resort", 20, vec![(CAITLYN, None), (ANNIE, None), (ASHE, None)])],
+                )
+            },
+            Case {
+                config: ChampSelectConfig {
+                    auto_pick_enabled: false,
+                    dodge_protection: dodge::DodgeProtectionConfig { last_resort_enabled: true, last_resort_threshold_ms: 5000, pool: vec![] },
+                    ..config()
+                },
+                owned: [ASHE, ANNIE].into_iter().collect(),
+                ..case(
+                    "last resort without a pool takes any pickable champion, even with auto-pick off",
+                    edited(session(pick_turn(0)), |s| s.timer.adjusted_time_left_in_phase = Some(1000)),
+                    vec![("last_resort", 20, vec![(ANNIE, None), (ASHE, None)])],
+                )
+            },
+            Case {
+                config: ChampSelectConfig {
+                    dodge_protection: dodge::DodgeProtectionConfig { last_resort_enabled: true, last_resort_threshold_ms: 5000, pool: vec![] },
+                    ..config()
+                },
+                ..case(
+                    "last resort waits until the threshold",
+                    session(pick_turn(0)),
+                    vec![("pick", 20, vec![(JINX, Some(1)), (ASHE, Some(2)), (CAITLYN, Some(3))])],
+                )
+            },
+        ]
+    }
+
+    #[test]
+    fn decide_table() {
+        let catalog = ChampionDataset::bundled().to_catalog();
+
+        for case in cases() {
+            let counter_picks = CounterPickTable { rules: case.counter_picks };
+            let plan = decide(&case.session, &case.config, &catalog, &case.owned, &counter_picks);
+            assert_eq!(summary(&plan), case.expected, "{}", case.name);
+        }
+    }
+
+    #[test]
+    fn candidates_carry_display_names_and_counter_rule() {
+        let catalog = ChampionDataset::bundled().to_catalog();
+        let rule = CounterPickRule { enemy: "Darius".to_string(), lane: None, prefer: vec!["Vayne".to_string()] };
+        let counter_picks = CounterPickTable { rules: vec![rule.clone()] };
+
+        let mut session = session(vec![vec![in_progress(action(20, ME, ActionType::Pick), 0)]]);
+        session.their_team[0].champion_id = DARIUS;
+
+        let plan = decide(&session, &config(), &catalog, &owned_all(), &counter_picks);
+        let PlannedAction::Pick { candidates, counter_rule, .. } = &plan[0] else {
+            panic!("expected a pick, got {:?}", plan);
+        };
+        assert_eq!(candidates[0].name, "Vayne");
+        assert_eq!(candidates[1].name, "Jinx");
+        assert_eq!(counter_rule.as_ref(), Some(&rule));
+    }
+}
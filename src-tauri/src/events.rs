@@ -0,0 +1,116 @@
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+/// Events kept for listeners that fall behind before they start missing some.
+const EVENT_BUFFER: usize = 64;
+
+/// Receives the events the monitoring loop reports (`match-accepted`,
+/// `champion-picked`, ...). The desktop app forwards them to the frontend;
+/// the CLI prints them.
+pub trait EventSink: Send + Sync {
+    fn send(&self, event: &str, payload: Value);
+}
+
+impl dyn EventSink + '_ {
+    pub fn emit<S: Serialize>(&self, event: &str, payload: S) {
+        match serde_json::to_value(payload) {
+            Ok(payload) => self.send(event, payload),
+            Err(e) => warn!(event, "Could not serialize event payload: {}", e),
+        }
+    }
+}
+
+/// Forwards to the frontend, and to the `EventBus`, webhooks, desktop
+/// notifications and tray when the app manages them.
+#[cfg(feature = "desktop")]
+impl EventSink for tauri::AppHandle {
+    fn send(&self, event: &str, payload: Value) {
+        use crate::notifications::Notifications;
+        use crate::tray::TrayMenu;
+        use crate::webhooks::Webhooks;
+        use tauri::Manager;
+
+        // Events also come from tray and shortcut handlers on the main
+        // thread, where webhook deliveries need the runtime entered to spawn.
+        let runtime = tauri::async_runtime::handle();
+        let _runtime = runtime.inner().enter();
+        if let Some(webhooks) = self.try_state::<Webhooks>() {
+            webhooks.notify(event, &payload);
+        }
+        if let Some(notifications) = self.try_state::<Notifications>() {
+            notifications.notify(self, event, &payload);
+        }
+        if let Some(tray) = self.try_state::<TrayMenu>() {
+            tray.on_event(self, event);
+        }
+        if let Some(bus) = self.try_state::<EventBus>() {
+            bus.publish(event, payload.clone());
+        }
+        if let Err(e) = tauri::Emitter::emit(self, event, payload) {
+            warn!(event, "Could not emit event: {}", e);
+        }
+    }
+}
+
+/// "match-accepted" → "Match accepted"
+pub fn title(event: &str) -> String {
+    let words = event.replace('-', " ");
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => words,
+    }
+}
+
+/// A one-line description of an event: the payload itself when it is a
+/// message, its `message` field, or the payload as JSON.
+pub fn describe(payload: &Value) -> String {
+    match payload {
+        Value::String(message) => message.clone(),
+        Value::Null => String::new(),
+        Value::Object(fields) => match fields.get("message").and_then(Value::as_str) {
+            Some(message) => message.to_string(),
+            None => payload.to_string(),
+        },
+        other => other.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceEvent {
+    pub timestamp: String,
+    pub event: String,
+    pub payload: Value,
+}
+
+/// Fans service events out to listeners outside the frontend, such as the
+/// control API's event stream. A listener that falls behind misses events
+/// instead of slowing the service down.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<ServiceEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUFFER);
+        Self { sender }
+    }
+}
+
+impl EventBus {
+    pub fn publish(&self, event: &str, payload: Value) {
+        // Only fails when nobody is listening.
+        let _ = self.sender.send(ServiceEvent {
+            timestamp: chrono::Local::now().to_rfc3339(),
+            event: event.to_string(),
+            payload,
+        });
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ServiceEvent> {
+        self.sender.subscribe()
+    }
+}
@@ -0,0 +1,346 @@
+use crate::league_client::LeagueError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use tracing::warn;
+
+pub const HISTORY_FILE: &str = "history.jsonl";
+
+/// Serializes appends from the monitoring loop and the commands.
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    Accept,
+    AcceptFailed,
+    Decline,
+    Pick,
+    PickFailed,
+    Ban,
+    BanFailed,
+    Dodge,
+    Disconnect,
+}
+
+impl ActivityKind {
+    pub fn is_failure(self) -> bool {
+        matches!(self, ActivityKind::AcceptFailed | ActivityKind::PickFailed | ActivityKind::BanFailed)
+    }
+}
+
+/// One line of `history.jsonl`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityRecord {
+    /// RFC 3339, local time.
+    pub timestamp: String,
+    pub kind: ActivityKind,
+    #[serde(default)]
+    pub queue_id: Option<i64>,
+    #[serde(default)]
+    pub champion_id: Option<i64>,
+    #[serde(default)]
+    pub champion_name: Option<String>,
+    /// 1-based position in the pick or ban priority list that was used.
+    #[serde(default)]
+    pub priority_slot: Option<usize>,
+    /// Time from spotting the ready check to accepting it, including any configured delay.
+    #[serde(default)]
+    pub accept_latency_ms: Option<i64>,
+    pub message: String,
+}
+
+impl ActivityRecord {
+    pub fn new(kind: ActivityKind, message: impl Into<String>) -> Self {
+        Self {
+            timestamp: chrono::Local::now().to_rfc3339(),
+            kind,
+            queue_id: None,
+            champion_id: None,
+            champion_name: None,
+            priority_slot: None,
+            accept_latency_ms: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn queue(mut self, queue_id: Option<i64>) -> Self {
+        self.queue_id = queue_id;
+        self
+    }
+
+    pub fn champion(mut self, champion_id: i64, champion_name: &str) -> Self {
+        self.champion_id = Some(champion_id);
+        self.champion_name = Some(champion_name.to_string());
+        self
+    }
+
+    pub fn priority_slot(mut self, slot: Option<usize>) -> Self {
+        self.priority_slot = slot;
+        self
+    }
+
+    pub fn accept_latency_ms(mut self, latency_ms: i64) -> Self {
+        self.accept_latency_ms = Some(latency_ms);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HistoryFilter {
+    /// Only these kinds. Empty means all.
+    #[serde(default)]
+    pub kinds: Vec<ActivityKind>,
+    #[serde(default)]
+    pub queue_id: Option<i64>,
+    #[serde(default)]
+    pub champion_id: Option<i64>,
+    /// RFC 3339 bounds, inclusive.
+    #[serde(default)]
+    pub since: Option<String>,
+    #[serde(default)]
+    pub until: Option<String>,
+    /// Keep only the most recent `limit` records.
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+impl HistoryFilter {
+    fn matches(&self, record: &ActivityRecord) -> bool {
+        let timestamp = chrono::DateTime::parse_from_rfc3339(&record.timestamp).ok();
+        let bound = |value: &Option<String>| value.as_deref().and_then(|v| chrono::DateTime::parse_from_rfc3339(v).ok());
+
+        (self.kinds.is_empty() || self.kinds.contains(&record.kind))
+            && self.queue_id.is_none_or(|id| record.queue_id == Some(id))
+            && self.champion_id.is_none_or(|id| record.champion_id == Some(id))
+            && bound(&self.since).is_none_or(|since| timestamp.is_some_and(|t| t >= since))
+            && bound(&self.until).is_none_or(|until| timestamp.is_some_and(|t| t <= until))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChampionPickStats {
+    pub champion_id: i64,
+    pub champion_name: String,
+    pub attempts: u32,
+    pub successes: u32,
+    pub success_rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SlotUsage {
+    pub slot: usize,
+    pub picks: u32,
+    pub bans: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct HistoryStats {
+    pub accepts: u32,
+    pub accept_failures: u32,
+    pub average_accept_latency_ms: Option<f64>,
+    pub fastest_accept_latency_ms: Option<i64>,
+    pub slowest_accept_latency_ms: Option<i64>,
+    pub picks: Vec<ChampionPickStats>,
+    pub priority_slots: Vec<SlotUsage>,
+    pub bans: u32,
+    pub dodges: u32,
+    pub disconnects: u32,
+    pub failures: u32,
+}
+
+impl HistoryStats {
+    pub fn from_records(records: &[ActivityRecord]) -> Self {
+        let mut stats = HistoryStats::default();
+        let mut latencies = Vec::new();
+        let mut picks: BTreeMap<i64, ChampionPickStats> = BTreeMap::new();
+        let mut slots: BTreeMap<usize, SlotUsage> = BTreeMap::new();
+
+        for record in records {
+            match record.kind {
+                ActivityKind::Accept => {
+                    stats.accepts += 1;
+                    latencies.extend(record.accept_latency_ms);
+                }
+                ActivityKind::AcceptFailed => stats.accept_failures += 1,
+                ActivityKind::Ban => stats.bans += 1,
+                ActivityKind::Dodge => stats.dodges += 1,
+                ActivityKind::Disconnect => stats.disconnects += 1,
+                _ => {}
+            }
+
+            if record.kind.is_failure() {
+                stats.failures += 1;
+            }
+
+            if let (ActivityKind::Pick | ActivityKind::PickFailed, Some(champion_id)) = (record.kind, record.champion_id) {
+                let entry = picks.entry(champion_id).or_insert_with(|| ChampionPickStats {
+                    champion_id,
+                    champion_name: record.champion_name.clone().unwrap_or_default(),
+                    attempts: 0,
+                    successes: 0,
+                    success_rate: 0.0,
+                });
+                entry.attempts += 1;
+                if record.kind == ActivityKind::Pick {
+                    entry.successes += 1;
+                }
+            }
+
+            if let Some(slot) = record.priority_slot {
+                let usage = slots.entry(slot).or_insert(SlotUsage { slot, picks: 0, bans: 0 });
+                match record.kind {
+                    ActivityKind::Pick => usage.picks += 1,
+                    ActivityKind::Ban => usage.bans += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        if !latencies.is_empty() {
+            stats.average_accept_latency_ms = Some(latencies.iter().sum::<i64>() as f64 / latencies.len() as f64);
+            stats.fastest_accept_latency_ms = latencies.iter().min().copied();
+            stats.slowest_accept_latency_ms = latencies.iter().max().copied();
+        }
+
+        stats.picks = picks
+            .into_values()
+            .map(|mut entry| {
+                entry.success_rate = entry.successes as f64 / entry.attempts as f64;
+                entry
+            })
+            .collect();
+        stats.picks.sort_by(|a, b| b.attempts.cmp(&a.attempts).then(a.champion_name.cmp(&b.champion_name)));
+        stats.priority_slots = slots.into_values().collect();
+
+        stats
+    }
+}
+
+pub fn append(path: &Path, record: &ActivityRecord) -> Result<(), LeagueError> {
+    let line = serde_json::to_string(record)
+        .map_err(|e| format!("Could not serialize activity record: {}", e))?;
+
+    let _guard = WRITE_LOCK.lock().unwrap();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)?;
+
+    Ok(())
+}
+
+/// Appends to the history file. Failing to record is logged, never fatal.
+pub fn record(path: &Path, record: ActivityRecord) {
+    if let Err(e) = append(path, &record) {
+        warn!("Could not record activity: {}", e);
+    }
+}
+
+/// Reads the records matching `filter`, oldest first. Lines that fail to
+/// parse (e.g. a write cut short by a crash) are skipped.
+pub fn load(path: &Path, filter: &HistoryFilter) -> Result<Vec<ActivityRecord>, LeagueError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path)?;
+    let mut records: Vec<ActivityRecord> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .filter(|record| filter.matches(record))
+        .collect();
+
+    if let Some(limit) = filter.limit {
+        let excess = records.len().saturating_sub(limit);
+        records.drain(..excess);
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(timestamp: &str, record: ActivityRecord) -> ActivityRecord {
+        ActivityRecord { timestamp: timestamp.to_string(), ..record }
+    }
+
+    fn records() -> Vec<ActivityRecord> {
+        vec![
+            at("2026-03-01T10:00:00+01:00", ActivityRecord::new(ActivityKind::Accept, "Match accepted").queue(Some(420)).accept_latency_ms(800)),
+            at("2026-03-01T10:01:00+01:00", ActivityRecord::new(ActivityKind::Ban, "Banned Yasuo").queue(Some(420)).champion(157, "Yasuo").priority_slot(Some(1))),
+            at("2026-03-01T10:02:00+01:00", ActivityRecord::new(ActivityKind::PickFailed, "Could not pick Jinx").queue(Some(420)).champion(222, "Jinx").priority_slot(Some(1))),
+            at("2026-03-01T10:02:05+01:00", ActivityRecord::new(ActivityKind::Pick, "Picked Ashe").queue(Some(420)).champion(22, "Ashe").priority_slot(Some(2))),
+            at("2026-03-02T20:00:00+01:00", ActivityRecord::new(ActivityKind::Accept, "Match accepted").queue(Some(440)).accept_latency_ms(3200)),
+            at("2026-03-02T20:02:00+01:00", ActivityRecord::new(ActivityKind::Pick, "Picked Jinx").queue(Some(440)).champion(222, "Jinx").priority_slot(Some(1))),
+            at("2026-03-02T20:10:00+01:00", ActivityRecord::new(ActivityKind::AcceptFailed, "Failed to accept match")),
+            at("2026-03-02T20:30:00+01:00", ActivityRecord::new(ActivityKind::Dodge, "Another player dodged").queue(Some(440))),
+        ]
+    }
+
+    fn matching(filter: &HistoryFilter) -> Vec<String> {
+        records().into_iter().filter(|record| filter.matches(record)).map(|record| record.message).collect()
+    }
+
+    #[test]
+    fn time_bounds_are_inclusive_and_compare_instants() {
+        let filter = HistoryFilter {
+            since: Some("2026-03-01T09:02:05Z".to_string()),
+            until: Some("2026-03-02T20:02:00+01:00".to_string()),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(matching(&filter), vec!["Picked Ashe", "Match accepted", "Picked Jinx"]);
+    }
+
+    #[test]
+    fn filters_combine() {
+        let filter = HistoryFilter {
+            kinds: vec![ActivityKind::Pick, ActivityKind::PickFailed],
+            champion_id: Some(222),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(matching(&filter), vec!["Could not pick Jinx", "Picked Jinx"]);
+
+        let ranked_flex = HistoryFilter { queue_id: Some(440), ..HistoryFilter::default() };
+        assert_eq!(matching(&ranked_flex).len(), 3);
+    }
+
+    #[test]
+    fn unparseable_timestamps_fail_time_bounds_only() {
+        let record = at("yesterday", ActivityRecord::new(ActivityKind::Accept, "Match accepted"));
+        assert!(HistoryFilter::default().matches(&record));
+        assert!(!HistoryFilter { since: Some("2026-01-01T00:00:00Z".to_string()), ..HistoryFilter::default() }.matches(&record));
+    }
+
+    #[test]
+    fn stats() {
+        let stats = HistoryStats::from_records(&records());
+
+        assert_eq!((stats.accepts, stats.accept_failures, stats.failures), (2, 1, 2));
+        assert_eq!(stats.average_accept_latency_ms, Some(2000.0));
+        assert_eq!((stats.fastest_accept_latency_ms, stats.slowest_accept_latency_ms), (Some(800), Some(3200)));
+        assert_eq!((stats.bans, stats.dodges, stats.disconnects), (1, 1, 0));
+
+        let picks: Vec<(&str, u32, u32, f64)> = stats.picks.iter()
+            .map(|pick| (pick.champion_name.as_str(), pick.attempts, pick.successes, pick.success_rate))
+            .collect();
+        assert_eq!(picks, vec![("Jinx", 2, 1, 0.5), ("Ashe", 1, 1, 1.0)]);
+
+        let slots: Vec<(usize, u32, u32)> = stats.priority_slots.iter().map(|usage| (usage.slot, usage.picks, usage.bans)).collect();
+        assert_eq!(slots, vec![(1, 1, 1), (2, 1, 0)]);
+    }
+
+    #[test]
+    fn no_latency_without_accepts() {
+        let stats = HistoryStats::from_records(&[]);
+        assert_eq!(stats.average_accept_latency_ms, None);
+        assert!(stats.picks.is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "desktop")]
+use {
+    std::sync::Mutex,
+    tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut},
+    tracing::{info, warn},
+};
+
+/// System-wide shortcuts, written like `CommandOrControl+Shift+A`. Unset
+/// actions have no shortcut.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct HotkeyConfig {
+    #[serde(default)]
+    pub toggle_auto_accept: Option<String>,
+    #[serde(default)]
+    pub manual_accept: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotkeyAction {
+    ToggleAutoAccept,
+    ManualAccept,
+}
+
+impl HotkeyAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            HotkeyAction::ToggleAutoAccept => "Toggle auto-accept",
+            HotkeyAction::ManualAccept => "Accept match",
+        }
+    }
+}
+
+#[cfg(feature = "desktop")]
+impl HotkeyConfig {
+    /// Parses the configured shortcuts, rejecting invalid ones and shortcuts
+    /// bound to more than one action.
+    pub fn bindings(&self) -> Result<Vec<(HotkeyAction, Shortcut)>, String> {
+        let configured = [
+            (HotkeyAction::ToggleAutoAccept, &self.toggle_auto_accept),
+            (HotkeyAction::ManualAccept, &self.manual_accept),
+        ];
+
+        let mut bindings: Vec<(HotkeyAction, Shortcut)> = Vec::new();
+        for (action, text) in configured {
+            let Some(text) = text.as_deref().map(str::trim).filter(|text| !text.is_empty()) else {
+                continue;
+            };
+            let shortcut: Shortcut = text.parse()
+                .map_err(|e| format!("Invalid shortcut '{}': {}", text, e))?;
+            if bindings.iter().any(|(_, bound)| *bound == shortcut) {
+                return Err(format!("'{}' is assigned to more than one action", text));
+            }
+            bindings.push((action, shortcut));
+        }
+        Ok(bindings)
+    }
+}
+
+/// The shortcuts currently registered with the OS and what they do.
+#[cfg(feature = "desktop")]
+#[derive(Default)]
+pub struct Hotkeys {
+    bindings: Mutex<Vec<(HotkeyAction, Shortcut)>>,
+}
+
+#[cfg(feature = "desktop")]
+impl Hotkeys {
+    pub fn action_for(&self, shortcut: &Shortcut) -> Option<HotkeyAction> {
+        self.bindings.lock().unwrap()
+            .iter()
+            .find(|(_, bound)| bound == shortcut)
+            .map(|(action, _)| *action)
+    }
+
+    /// Replaces the registered shortcuts with `config`'s. If one can't be
+    /// registered, usually because another application already uses it, the
+    /// previous shortcuts are restored and the conflict is returned.
+    pub fn apply(&self, app_handle: &tauri::AppHandle, config: &HotkeyConfig) -> Result<(), String> {
+        let bindings = config.bindings()?;
+        // Not held while registering: shortcut events look up actions meanwhile.
+        let previous = self.bindings.lock().unwrap().clone();
+
+        unregister(app_handle, &previous);
+        if let Err(e) = register(app_handle, &bindings) {
+            if let Err(restore) = register(app_handle, &previous) {
+                warn!("Could not restore the previous shortcuts: {}", restore);
+            }
+            return Err(e);
+        }
+
+        info!(count = bindings.len(), "Global shortcuts registered");
+        *self.bindings.lock().unwrap() = bindings;
+        Ok(())
+    }
+}
+
+/// Registers all of `bindings`, or none of them.
+#[cfg(feature = "desktop")]
+fn register(app_handle: &tauri::AppHandle, bindings: &[(HotkeyAction, Shortcut)]) -> Result<(), String> {
+    let global_shortcut = app_handle.global_shortcut();
+    for (index, (action, shortcut)) in bindings.iter().enumerate() {
+        if let Err(e) = global_shortcut.register(shortcut.clone()) {
+            unregister(app_handle, &bindings[..index]);
+            return Err(format!(
+                "Could not register the {} shortcut, another application may already use it: {}",
+                action.label(),
+                e
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "desktop")]
+fn unregister(app_handle: &tauri::AppHandle, bindings: &[(HotkeyAction, Shortcut)]) {
+    for (_, shortcut) in bindings {
+        if let Err(e) = app_handle.global_shortcut().unregister(shortcut.clone()) {
+            warn!("Could not unregister a shortcut: {}", e);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "desktop"))]
+mod tests {
+    use super::*;
+
+    fn config(toggle: Option<&str>, accept: Option<&str>) -> HotkeyConfig {
+        HotkeyConfig {
+            toggle_auto_accept: toggle.map(String::from),
+            manual_accept: accept.map(String::from),
+        }
+    }
+
+    #[test]
+    fn bindings_skip_unset_actions() {
+        let bindings = config(Some("Ctrl+Shift+A"), Some("  ")).bindings().unwrap();
+        assert_eq!(bindings.len(), 1);
+        assert_eq!(bindings[0].0, HotkeyAction::ToggleAutoAccept);
+    }
+
+    #[test]
+    fn one_shortcut_per_action() {
+        let error = config(Some("Ctrl+Shift+A"), Some("Ctrl+Shift+A")).bindings().unwrap_err();
+        assert!(error.contains("more than one action"), "{}", error);
+    }
+
+    #[test]
+    fn invalid_shortcuts_are_rejected() {
+        let error = config(None, Some("Ctrl+Shift+NotAKey")).bindings().unwrap_err();
+        assert!(error.starts_with("Invalid shortcut 'Ctrl+Shift+NotAKey'"), "{}", error);
+    }
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct InviteConfig {
+    #[serde(default)]
+    pub auto_accept_enabled: bool,
+    /// Summoner names, Riot IDs (`Name#TAG`) or PUUIDs of friends whose invites are accepted.
+    #[serde(default)]
+    pub whitelist: Vec<String>,
+    /// Decline invites from anyone not on the whitelist.
+    #[serde(default)]
+    pub decline_others: bool,
+}
+
+/// What we know about whoever sent an invitation.
+#[derive(Debug, Clone, Default)]
+pub struct Inviter {
+    pub summoner_name: Option<String>,
+    pub game_name: Option<String>,
+    pub tag_line: Option<String>,
+    pub puuid: Option<String>,
+}
+
+impl Inviter {
+    pub fn display_name(&self) -> String {
+        match (&self.game_name, &self.tag_line) {
+            (Some(name), Some(tag)) if !name.is_empty() && !tag.is_empty() => format!("{}#{}", name, tag),
+            (Some(name), _) if !name.is_empty() => name.clone(),
+            _ => self.summoner_name.clone().unwrap_or_else(|| "Unknown".to_string()),
+        }
+    }
+}
+
+impl InviteConfig {
+    pub fn is_whitelisted(&self, inviter: &Inviter) -> bool {
+        let riot_id = match (&inviter.game_name, &inviter.tag_line) {
+            (Some(name), Some(tag)) => Some(format!("{}#{}", name, tag)),
+            _ => None,
+        };
+
+        let candidates = [
+            inviter.summoner_name.as_deref(),
+            inviter.game_name.as_deref(),
+            riot_id.as_deref(),
+            inviter.puuid.as_deref(),
+        ];
+
+        self.whitelist.iter().map(|entry| entry.trim()).any(|entry| {
+            !entry.is_empty()
+                && candidates
+                    .iter()
+                    .flatten()
+                    .any(|candidate| candidate.eq_ignore_ascii_case(entry))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InviteEvent {
+    pub inviter: String,
+    pub invitation_id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn whitelist(entries: &[&str]) -> InviteConfig {
+        InviteConfig {
+            auto_accept_enabled: true,
+            whitelist: entries.iter().map(|entry| entry.to_string()).collect(),
+            decline_others: false,
+        }
+    }
+
+    fn inviter() -> Inviter {
+        Inviter {
+            summoner_name: Some("OldName".to_string()),
+            game_name: Some("Faker".to_string()),
+            tag_line: Some("KR1".to_string()),
+            puuid: Some("abc-123".to_string()),
+        }
+    }
+
+    #[test]
+    fn matches_any_identifier_ignoring_case() {
+        for entry in ["faker#kr1", "FAKER", "oldname", "ABC-123", "  Faker#KR1  "] {
+            assert!(whitelist(&[entry]).is_whitelisted(&inviter()), "{}", entry);
+        }
+    }
+
+    #[test]
+    fn riot_ids_need_the_right_tag() {
+        assert!(!whitelist(&["Faker#EUW"]).is_whitelisted(&inviter()));
+
+        let without_tag = Inviter { tag_line: None, ..inviter() };
+        assert!(!whitelist(&["Faker#KR1"]).is_whitelisted(&without_tag));
+        assert!(whitelist(&["Faker"]).is_whitelisted(&without_tag));
+    }
+
+    #[test]
+    fn blank_entries_match_nobody() {
+        assert!(!whitelist(&["", "  "]).is_whitelisted(&Inviter::default()));
+        assert!(!whitelist(&[]).is_whitelisted(&inviter()));
+    }
+
+    #[test]
+    fn display_name_prefers_the_riot_id() {
+        assert_eq!(inviter().display_name(), "Faker#KR1");
+        assert_eq!(Inviter { tag_line: None, ..inviter() }.display_name(), "Faker");
+        assert_eq!(Inviter { game_name: None, ..inviter() }.display_name(), "OldName");
+        assert_eq!(Inviter::default().display_name(), "Unknown");
+    }
+}
@@ -1,12 +1,34 @@
-use reqwest::Client;
+use reqwest::{Client, Method, Response};
 use serde_json::{Value, json};
 use std::fs;
 use std::path::Path;
 use tokio::time::{sleep, Duration};
 use base64::{Engine as _, engine::general_purpose};
 use std::env;
-use tauri::Emitter;
 use rand::Rng;
+use std::collections::HashSet;
+use crate::champ_select::{ActionType, ChampSelectSession};
+use crate::champion_catalog::{self, ChampionCatalog, ChampionDataset, ChampionSummary};
+use crate::config_store::{AppSettings, UnresolvedChampion};
+use crate::control_api::ControlApiConfig;
+use crate::hotkeys::HotkeyConfig;
+use crate::notifications::NotificationConfig;
+use crate::webhooks::WebhookConfig;
+use crate::counter_picks::{CounterPickCache, CounterPickRule};
+use crate::draft::{self, Candidate, PlannedAction};
+use crate::events::EventSink;
+use crate::dodge::{ChampSelectTracker, DodgeProtectionConfig, DodgeRecord};
+use crate::history::{self, ActivityKind, ActivityRecord};
+use crate::invites::{InviteConfig, InviteEvent, Inviter};
+use crate::lobby::LobbyConfig;
+use crate::paths::AppPaths;
+use crate::secret::Secret;
+use crate::post_game::{PostGameConfig, PostGameProgress, PostGameStep, PostGameTracker};
+use crate::ready_check::{ReadyCheckControl, ReadyCheckCountdown, ReadyCheckDecision, ReadyCheckPolicy};
+use crate::reconnect::{Backoff, ReconnectConfig};
+use crate::request_policy::{Failure, RetryPolicy};
+use std::sync::Arc;
+use tracing::{debug, info, info_span, trace, warn, Instrument, Span};
 
 #[derive(Debug)]
 pub struct LeagueError {
@@ -55,8 +77,38 @@ impl From<std::num::ParseIntError> for LeagueError {
 pub struct ChampSelectConfig {
     pub auto_pick_enabled: bool,
     pub auto_ban_enabled: bool,
-    pub pick_priority: Vec<String>,
-    pub ban_priority: Vec<String>,
+    /// Champion IDs, most preferred first. Names are looked up for display only.
+    pub pick_priority: Vec<i64>,
+    pub ban_priority: Vec<i64>,
+    #[serde(default)]
+    pub ready_check: ReadyCheckPolicy,
+    #[serde(default)]
+    pub post_game: PostGameConfig,
+    #[serde(default)]
+    pub invites: InviteConfig,
+    #[serde(default)]
+    pub lobby: LobbyConfig,
+    #[serde(default)]
+    pub dodge_protection: DodgeProtectionConfig,
+    #[serde(default)]
+    pub app: AppSettings,
+    #[serde(default)]
+    pub control_api: ControlApiConfig,
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+    #[serde(default)]
+    pub hotkeys: HotkeyConfig,
+    #[serde(default)]
+    pub reconnect: ReconnectConfig,
+    /// Start auto-accept whenever the League client starts.
+    #[serde(default)]
+    pub start_with_client: bool,
+    /// Names from an old config that could not be converted to champion IDs.
+    /// Shown in the settings until they are next saved.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub legacy_unresolved: Vec<UnresolvedChampion>,
 }
 
 impl Default for ChampSelectConfig {
@@ -64,24 +116,70 @@ impl Default for ChampSelectConfig {
         Self {
             auto_pick_enabled: false,
             auto_ban_enabled: false,
-            pick_priority: vec!["Jinx".to_string(), "Ashe".to_string(), "Caitlyn".to_string()],
-            ban_priority: vec!["Yasuo".to_string(), "Zed".to_string(), "Master Yi".to_string()],
+            // Jinx, Ashe, Caitlyn
+            pick_priority: vec![222, 22, 51],
+            // Yasuo, Zed, Master Yi
+            ban_priority: vec![157, 238, 11],
+            ready_check: ReadyCheckPolicy::default(),
+            post_game: PostGameConfig::default(),
+            invites: InviteConfig::default(),
+            lobby: LobbyConfig::default(),
+            dodge_protection: DodgeProtectionConfig::default(),
+            app: AppSettings::default(),
+            control_api: ControlApiConfig::default(),
+            webhooks: Vec::new(),
+            notifications: NotificationConfig::default(),
+            hotkeys: HotkeyConfig::default(),
+            reconnect: ReconnectConfig::default(),
+            start_with_client: false,
+            legacy_unresolved: Vec::new(),
         }
     }
 }
 
-#[derive(Debug)]
-pub struct ChampSelectPhase {
-    pub phase: String,
-    pub is_in_progress: bool,
-    pub local_player_cell_id: i64,
-    pub actions: Vec<Value>,
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ChampionPickedPayload {
+    pub message: String,
+    pub champion: String,
+    pub champion_id: i64,
+    pub counter_rule: Option<CounterPickRule>,
+}
+
+/// What the client made of a write it answered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WriteOutcome {
+    Done,
+    /// The client turned it down (4xx), e.g. a champion that is already
+    /// banned. Holds the method, path, status and the client's response body.
+    Rejected(String),
+}
+
+impl WriteOutcome {
+    pub fn is_done(&self) -> bool {
+        *self == WriteOutcome::Done
+    }
+}
+
+/// The outcome of `AutoAcceptService::reconnect`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connection {
+    Restored,
+    /// The client exited and didn't come back in time; monitoring stops.
+    Closed,
+    /// The client seems to be running but couldn't be reached in time.
+    Lost,
 }
 
+/// How often to look for a new lockfile while waiting for the client.
+const LOCKFILE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 pub struct LeagueClient {
     client: Client,
     base_url: String,
-    auth_header: String,
+    auth_header: Secret,
+    catalog: tokio::sync::OnceCell<ChampionCatalog>,
+    /// `RetryPolicy::for_method`; tests shorten the timeouts.
+    retry_policy: fn(&Method) -> RetryPolicy,
 }
 
 impl LeagueClient {
@@ -95,23 +193,38 @@ impl LeagueClient {
         }
         
         let port: u16 = parts[2].parse()?;
-        let password = parts[3];
+        let password = Secret::new(parts[3]);
         
         let client = Client::builder()
             .danger_accept_invalid_certs(true)
             .build()?;
         
-        let auth = format!("riot:{}", password);
+        let auth = format!("riot:{}", password.expose());
         let auth_encoded = general_purpose::STANDARD.encode(auth.as_bytes());
-        let auth_header = format!("Basic {}", auth_encoded);
+        let auth_header = Secret::new(format!("Basic {}", auth_encoded));
         
         Ok(LeagueClient {
             client,
             base_url: format!("https://127.0.0.1:{}", port),
             auth_header,
+            catalog: tokio::sync::OnceCell::new(),
+            retry_policy: RetryPolicy::for_method,
         })
     }
     
+    /// A client for a `StandIn` server at `base_url`, with timeouts short
+    /// enough to run out in a test.
+    #[cfg(test)]
+    pub(crate) fn for_stand_in(base_url: String) -> Self {
+        LeagueClient {
+            client: Client::new(),
+            base_url,
+            auth_header: Secret::new("Basic test"),
+            catalog: tokio::sync::OnceCell::new(),
+            retry_policy: |method| RetryPolicy { timeout: Duration::from_millis(200), ..RetryPolicy::for_method(method) },
+        }
+    }
+    
     fn find_lockfile() -> Result<String, LeagueError> {
         let mut possible_paths = vec![
             "C:\\Riot Games\\League of Legends\\lockfile".to_string(),
@@ -136,14 +249,101 @@ impl LeagueClient {
         Err("League Client lockfile not found. Is League of Legends running?".into())
     }
     
+    /// Whether the client is running. The client writes its lockfile on start
+    /// and removes it on exit.
+    pub fn lockfile_present() -> bool {
+        Self::find_lockfile().is_ok()
+    }
+    
+    /// The lockfile's contents. A new client process writes a new port and password.
+    fn read_lockfile() -> Option<String> {
+        fs::read_to_string(Self::find_lockfile().ok()?).ok()
+    }
+    
+    /// Sleeps for `delay`, or until a lockfile other than `stale` appears.
+    async fn wait_for_new_lockfile(delay: Duration, stale: &Option<String>) {
+        let deadline = std::time::Instant::now() + delay;
+        loop {
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            if remaining.is_zero() {
+                return;
+            }
+            sleep(remaining.min(LOCKFILE_POLL_INTERVAL)).await;
+            
+            let current = Self::read_lockfile();
+            if current.is_some() && current != *stale {
+                debug!("New League Client lockfile found");
+                return;
+            }
+        }
+    }
+    
+    /// Sends a request with its method's timeout, retrying the failures its
+    /// `RetryPolicy` allows. The last response is returned whatever its status.
+    async fn send(&self, method: Method, url: &str, body: Option<&Value>) -> Result<Response, LeagueError> {
+        let policy = (self.retry_policy)(&method);
+        let mut attempt = 1;
+        loop {
+            let mut request = self.client
+                .request(method.clone(), url)
+                .header("Authorization", self.auth_header.expose())
+                .timeout(policy.timeout);
+            if let Some(body) = body {
+                request = request.json(body);
+            }
+            
+            let result = request.send().await;
+            let failure = match &result {
+                Ok(response) => Failure::from_status(response.status()),
+                Err(e) => Some(Failure::from_error(e)),
+            };
+            match failure {
+                Some(failure) if attempt < policy.max_attempts && policy.retries(failure) => {
+                    debug!(%method, path = self.path(url), attempt, ?failure, "League Client request failed, retrying");
+                    sleep(policy.delay_after(attempt)).await;
+                    attempt += 1;
+                }
+                _ => return Ok(result?),
+            }
+        }
+    }
+    
+    /// Sends a write. A rejection (4xx) and a server error that outlasts the
+    /// retries both carry the client's response body.
+    async fn write(&self, method: Method, url: &str, body: Option<&Value>) -> Result<WriteOutcome, LeagueError> {
+        let response = self.send(method.clone(), url, body).await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(WriteOutcome::Done);
+        }
+        
+        let response_text = response.text().await.unwrap_or_default();
+        let message = format!("{} {} failed with {}: {}", method, self.path(url), status, response_text.trim());
+        if status.is_server_error() {
+            return Err(message.into());
+        }
+        Ok(WriteOutcome::Rejected(message))
+    }
+    
+    /// Sends a write whose rejection callers only need as `false`.
+    async fn write_done(&self, method: Method, url: &str, body: Option<&Value>) -> Result<bool, LeagueError> {
+        match self.write(method, url, body).await? {
+            WriteOutcome::Done => Ok(true),
+            WriteOutcome::Rejected(message) => {
+                debug!("{}", message);
+                Ok(false)
+            }
+        }
+    }
+    
+    fn path<'a>(&self, url: &'a str) -> &'a str {
+        url.strip_prefix(self.base_url.as_str()).unwrap_or(url)
+    }
+    
     pub async fn is_in_ready_check(&self) -> Result<bool, LeagueError> {
         let url = format!("{}/lol-matchmaking/v1/ready-check", self.base_url);
         
-        let response = self.client
-            .get(&url)
-            .header("Authorization", &self.auth_header)
-            .send()
-            .await?;
+        let response = self.send(Method::GET, &url, None).await?;
         
         if response.status().is_success() {
             let json: Value = response.json().await?;
@@ -155,26 +355,190 @@ impl LeagueClient {
         Ok(false)
     }
     
-    pub async fn accept_ready_check(&self) -> Result<bool, LeagueError> {
+    pub async fn accept_ready_check(&self) -> Result<WriteOutcome, LeagueError> {
         let url = format!("{}/lol-matchmaking/v1/ready-check/accept", self.base_url);
         
-        let response = self.client
-            .post(&url)
-            .header("Authorization", &self.auth_header)
-            .send()
-            .await?;
+        self.write(Method::POST, &url, None).await
+    }
+    
+    pub async fn decline_ready_check(&self) -> Result<WriteOutcome, LeagueError> {
+        let url = format!("{}/lol-matchmaking/v1/ready-check/decline", self.base_url);
+        
+        self.write(Method::POST, &url, None).await
+    }
+    
+    pub async fn get_gameflow_session(&self) -> Result<Option<Value>, LeagueError> {
+        let url = format!("{}/lol-gameflow/v1/session", self.base_url);
+        
+        let response = self.send(Method::GET, &url, None).await?;
+        
+        if response.status().is_success() {
+            let json: Value = response.json().await?;
+            Ok(Some(json))
+        } else if response.status().as_u16() == 404 {
+            Ok(None)
+        } else {
+            Err("Failed to get gameflow session".into())
+        }
+    }
+    
+    pub async fn get_current_queue_id(&self) -> Result<Option<i64>, LeagueError> {
+        let queue_id = self.get_gameflow_session().await?
+            .and_then(|session| session.pointer("/gameData/queue/id").and_then(|id| id.as_i64()));
+        
+        Ok(queue_id)
+    }
+    
+    pub async fn get_gameflow_phase(&self) -> Result<String, LeagueError> {
+        let url = format!("{}/lol-gameflow/v1/gameflow-phase", self.base_url);
+        
+        let response = self.send(Method::GET, &url, None).await?;
         
-        Ok(response.status().is_success())
+        if response.status().is_success() {
+            let phase: String = response.json().await?;
+            Ok(phase)
+        } else {
+            Err("Failed to get gameflow phase".into())
+        }
+    }
+    
+    pub async fn skip_honor_vote(&self, game_id: i64) -> Result<bool, LeagueError> {
+        let url = format!("{}/lol-honor-v2/v1/honor-player", self.base_url);
+        
+        let payload = json!({
+            "gameId": game_id,
+            "honorCategory": "",
+            "summonerId": 0
+        });
+        
+        self.write_done(Method::POST, &url, Some(&payload)).await
+    }
+    
+    pub async fn dismiss_stats(&self) -> Result<bool, LeagueError> {
+        let url = format!("{}/lol-end-of-game/v1/state/dismiss-stats", self.base_url);
+        
+        self.write_done(Method::POST, &url, None).await
+    }
+    
+    pub async fn play_again(&self) -> Result<bool, LeagueError> {
+        let url = format!("{}/lol-lobby/v2/play-again", self.base_url);
+        
+        self.write_done(Method::POST, &url, None).await
+    }
+    
+    pub async fn get_lobby(&self) -> Result<Option<Value>, LeagueError> {
+        let url = format!("{}/lol-lobby/v2/lobby", self.base_url);
+        
+        let response = self.send(Method::GET, &url, None).await?;
+        
+        if response.status().is_success() {
+            let json: Value = response.json().await?;
+            Ok(Some(json))
+        } else if response.status().as_u16() == 404 {
+            Ok(None)
+        } else {
+            Err("Failed to get lobby".into())
+        }
+    }
+    
+    pub async fn is_lobby_leader(&self) -> Result<bool, LeagueError> {
+        let is_leader = self.get_lobby().await?
+            .and_then(|lobby| lobby.pointer("/localMember/isLeader").and_then(|l| l.as_bool()))
+            .unwrap_or(false);
+        
+        Ok(is_leader)
+    }
+    
+    pub async fn create_lobby(&self, queue_id: i64) -> Result<bool, LeagueError> {
+        let url = format!("{}/lol-lobby/v2/lobby", self.base_url);
+        
+        let payload = json!({ "queueId": queue_id });
+        
+        self.write_done(Method::POST, &url, Some(&payload)).await
+    }
+    
+    pub async fn set_position_preferences(&self, first: &str, second: &str) -> Result<bool, LeagueError> {
+        let url = format!("{}/lol-lobby/v2/lobby/members/localMember/position-preferences", self.base_url);
+        
+        let payload = json!({
+            "firstPreference": first,
+            "secondPreference": second
+        });
+        
+        self.write_done(Method::PUT, &url, Some(&payload)).await
+    }
+    
+    pub async fn get_friends(&self) -> Result<Vec<Value>, LeagueError> {
+        let url = format!("{}/lol-chat/v1/friends", self.base_url);
+        
+        let response = self.send(Method::GET, &url, None).await?;
+        
+        if response.status().is_success() {
+            let json: Vec<Value> = response.json().await?;
+            Ok(json)
+        } else {
+            Err("Failed to get friends list".into())
+        }
+    }
+    
+    pub async fn invite_summoners(&self, summoner_ids: &[i64]) -> Result<bool, LeagueError> {
+        let url = format!("{}/lol-lobby/v2/lobby/invitations", self.base_url);
+        
+        let payload = Value::Array(summoner_ids.iter()
+            .map(|id| json!({ "toSummonerId": id }))
+            .collect());
+        
+        self.write_done(Method::POST, &url, Some(&payload)).await
+    }
+    
+    pub async fn start_matchmaking(&self) -> Result<bool, LeagueError> {
+        let url = format!("{}/lol-lobby/v2/lobby/matchmaking/search", self.base_url);
+        
+        self.write_done(Method::POST, &url, None).await
+    }
+    
+    pub async fn get_received_invitations(&self) -> Result<Vec<Value>, LeagueError> {
+        let url = format!("{}/lol-lobby/v2/received-invitations", self.base_url);
+        
+        let response = self.send(Method::GET, &url, None).await?;
+        
+        if response.status().is_success() {
+            let json: Vec<Value> = response.json().await?;
+            Ok(json)
+        } else {
+            Err("Failed to get received invitations".into())
+        }
+    }
+    
+    pub async fn accept_invitation(&self, invitation_id: &str) -> Result<bool, LeagueError> {
+        let url = format!("{}/lol-lobby/v2/received-invitations/{}/accept", self.base_url, invitation_id);
+        
+        self.write_done(Method::POST, &url, None).await
+    }
+    
+    pub async fn decline_invitation(&self, invitation_id: &str) -> Result<bool, LeagueError> {
+        let url = format!("{}/lol-lobby/v2/received-invitations/{}/decline", self.base_url, invitation_id);
+        
+        self.write_done(Method::POST, &url, None).await
+    }
+    
+    pub async fn get_summoner_by_id(&self, summoner_id: i64) -> Result<Value, LeagueError> {
+        let url = format!("{}/lol-summoner/v1/summoners/{}", self.base_url, summoner_id);
+        
+        let response = self.send(Method::GET, &url, None).await?;
+        
+        if response.status().is_success() {
+            let json: Value = response.json().await?;
+            Ok(json)
+        } else {
+            Err(format!("Failed to get summoner {}", summoner_id).into())
+        }
     }
     
     pub async fn get_summoner_info(&self) -> Result<Value, LeagueError> {
         let url = format!("{}/lol-summoner/v1/current-summoner", self.base_url);
         
-        let response = self.client
-            .get(&url)
-            .header("Authorization", &self.auth_header)
-            .send()
-            .await?;
+        let response = self.send(Method::GET, &url, None).await?;
         
         if response.status().is_success() {
             let json: Value = response.json().await?;
@@ -184,18 +548,26 @@ impl LeagueClient {
         }
     }
     
-    pub async fn get_champ_select_session(&self) -> Result<Option<Value>, LeagueError> {
+    /// The Riot ID (`Name#TAG`), or the legacy display name for accounts without one.
+    pub async fn get_summoner_name(&self) -> Result<String, LeagueError> {
+        let summoner = self.get_summoner_info().await?;
+        let field = |name: &str| summoner.get(name).and_then(|v| v.as_str()).filter(|s| !s.is_empty());
+        
+        Ok(match (field("gameName"), field("tagLine")) {
+            (Some(game_name), Some(tag_line)) => format!("{}#{}", game_name, tag_line),
+            (Some(game_name), None) => game_name.to_string(),
+            _ => field("displayName").unwrap_or("Unknown").to_string(),
+        })
+    }
+    
+    pub async fn get_champ_select_session(&self) -> Result<Option<ChampSelectSession>, LeagueError> {
         let url = format!("{}/lol-champ-select/v1/session", self.base_url);
         
-        let response = self.client
-            .get(&url)
-            .header("Authorization", &self.auth_header)
-            .send()
-            .await?;
+        let response = self.send(Method::GET, &url, None).await?;
         
         if response.status().is_success() {
-            let json: Value = response.json().await?;
-            Ok(Some(json))
+            let session: ChampSelectSession = response.json().await?;
+            Ok(Some(session))
         } else if response.status().as_u16() == 404 {
             Ok(None)
         } else {
@@ -203,14 +575,23 @@ impl LeagueClient {
         }
     }
     
+    pub async fn get_pickable_champion_ids(&self) -> Result<Vec<i64>, LeagueError> {
+        let url = format!("{}/lol-champ-select/v1/pickable-champion-ids", self.base_url);
+        
+        let response = self.send(Method::GET, &url, None).await?;
+        
+        if response.status().is_success() {
+            let json: Vec<i64> = response.json().await?;
+            Ok(json)
+        } else {
+            Err("Failed to get pickable champions".into())
+        }
+    }
+    
     pub async fn get_available_champions(&self) -> Result<Vec<Value>, LeagueError> {
         let url = format!("{}/lol-champions/v1/owned-champions-minimal", self.base_url);
         
-        let response = self.client
-            .get(&url)
-            .header("Authorization", &self.auth_header)
-            .send()
-            .await?;
+        let response = self.send(Method::GET, &url, None).await?;
         
         if response.status().is_success() {
             let json: Vec<Value> = response.json().await?;
@@ -220,7 +601,7 @@ impl LeagueClient {
         }
     }
     
-    pub async fn pick_champion(&self, action_id: i64, champion_id: i64) -> Result<bool, LeagueError> {
+    pub async fn pick_champion(&self, action_id: i64, champion_id: i64) -> Result<WriteOutcome, LeagueError> {
         let url = format!("{}/lol-champ-select/v1/session/actions/{}", self.base_url, action_id);
         
         let payload = json!({
@@ -229,25 +610,13 @@ impl LeagueClient {
             "type": "pick"
         });
         
-        println!("DEBUG: Attempting to pick champion {} with action ID {}", champion_id, action_id);
-        println!("DEBUG: Payload: {}", payload);
-        
-        let response = self.client
-            .patch(&url)
-            .header("Authorization", &self.auth_header)
-            .header("Content-Type", "application/json")
-            .json(&payload)
-            .send()
-            .await?;
+        debug!("Attempting to pick champion {} with action ID {}", champion_id, action_id);
+        trace!("Payload: {}", payload);
         
-        let status = response.status();
-        let response_text = response.text().await.unwrap_or_default();
-        println!("DEBUG: Pick response status: {}, body: {}", status, response_text);
-        
-        Ok(status.is_success())
+        self.write(Method::PATCH, &url, Some(&payload)).await
     }
     
-    pub async fn ban_champion(&self, action_id: i64, champion_id: i64) -> Result<bool, LeagueError> {
+    pub async fn ban_champion(&self, action_id: i64, champion_id: i64) -> Result<WriteOutcome, LeagueError> {
         let url = format!("{}/lol-champ-select/v1/session/actions/{}", self.base_url, action_id);
         
         let payload = json!({
@@ -256,343 +625,182 @@ impl LeagueClient {
             "type": "ban"
         });
         
-        println!("DEBUG: Attempting to ban champion {} with action ID {}", champion_id, action_id);
-        println!("DEBUG: Payload: {}", payload);
+        debug!("Attempting to ban champion {} with action ID {}", champion_id, action_id);
+        trace!("Payload: {}", payload);
         
-        let response = self.client
-            .patch(&url)
-            .header("Authorization", &self.auth_header)
-            .header("Content-Type", "application/json")
-            .json(&payload)
-            .send()
-            .await?;
+        self.write(Method::PATCH, &url, Some(&payload)).await
+    }
+    
+    pub async fn get_game_version(&self) -> Result<String, LeagueError> {
+        let url = format!("{}/lol-patch/v1/game-version", self.base_url);
         
-        let status = response.status();
-        let response_text = response.text().await.unwrap_or_default();
-        println!("DEBUG: Ban response status: {}, body: {}", status, response_text);
-        
-        Ok(status.is_success())
-    }
-    
-    pub async fn get_all_champion_names(&self) -> Result<Vec<String>, LeagueError> {
-        let url = format!("{}/lol-game-data/assets/v1/champions.json", self.base_url);
-        
-        let response = self.client
-            .get(&url)
-            .header("Authorization", &self.auth_header)
-            .send()
-            .await;
-        
-        if let Ok(response) = response {
-            if response.status().is_success() {
-                if let Ok(json) = response.json::<Value>().await {
-                    let mut champion_names = Vec::new();
-                    
-                    if let Some(champions) = json.as_object() {
-                        for (_, champion_data) in champions {
-                            if let Some(name) = champion_data.get("name").and_then(|n| n.as_str()) {
-                                champion_names.push(name.to_string());
-                            }
-                        }
-                    }
-                    
-                    if !champion_names.is_empty() {
-                        champion_names.sort();
-                        return Ok(champion_names);
-                    }
-                }
+        let response = self.send(Method::GET, &url, None).await?;
+        
+        if response.status().is_success() {
+            let version: String = response.json().await?;
+            Ok(version)
+        } else {
+            Err("Failed to get game version".into())
+        }
+    }
+    
+    pub async fn get_locale(&self) -> Result<String, LeagueError> {
+        let url = format!("{}/riotclient/region-locale", self.base_url);
+        
+        let response = self.send(Method::GET, &url, None).await?;
+        
+        if response.status().is_success() {
+            let json: Value = response.json().await?;
+            Ok(json.get("locale").and_then(|l| l.as_str()).unwrap_or_default().to_string())
+        } else {
+            Err("Failed to get client locale".into())
+        }
+    }
+    
+    pub async fn get_champion_summary(&self) -> Result<Vec<Value>, LeagueError> {
+        let url = format!("{}/lol-game-data/assets/v1/champion-summary.json", self.base_url);
+        
+        let response = self.send(Method::GET, &url, None).await?;
+        
+        if response.status().is_success() {
+            let json: Vec<Value> = response.json().await?;
+            Ok(json)
+        } else {
+            Err("Failed to get champion summary".into())
+        }
+    }
+    
+    /// The champion catalog for this connection, loaded on first use.
+    pub async fn champion_catalog(&self) -> Result<&ChampionCatalog, LeagueError> {
+        self.catalog.get_or_try_init(|| self.load_champion_catalog()).await
+    }
+    
+    /// Uses the on-disk catalog if it was built for the running client version and
+    /// language, otherwise rebuilds it from game data. A stale cache is still
+    /// better than nothing when the game-data endpoint is slow or unavailable.
+    async fn load_champion_catalog(&self) -> Result<ChampionCatalog, LeagueError> {
+        let version = self.get_game_version().await.unwrap_or_default();
+        let locale = self.get_locale().await.unwrap_or_default();
+        let cache_path = champion_catalog::cache_path();
+        let cached = cache_path.as_deref().and_then(ChampionCatalog::load_cached);
+        
+        if let Some(catalog) = cached.as_ref() {
+            if !version.is_empty() && catalog.version == version && catalog.locale == locale {
+                debug!("Using cached champion catalog for version {}", version);
+                return Ok(catalog.clone());
             }
         }
         
-        let mut champions: Vec<String> = vec![
-            "Aatrox", "Ahri", "Akali", "Akshan", "Alistar", "Amumu", "Anivia", "Annie", 
-            "Aphelios", "Ashe", "Aurelion Sol", "Azir", "Bard", "Bel'Veth", "Blitzcrank", 
-            "Brand", "Braum", "Caitlyn", "Camille", "Cassiopeia", "Cho'Gath", "Corki", 
-            "Darius", "Diana", "Dr. Mundo", "Draven", "Ekko", "Elise", "Evelynn", "Ezreal", 
-            "Fiddlesticks", "Fiora", "Fizz", "Galio", "Gangplank", "Garen", "Gnar", 
-            "Gragas", "Graves", "Gwen", "Hecarim", "Heimerdinger", "Illaoi", "Irelia", 
-            "Ivern", "Janna", "Jarvan IV", "Jax", "Jayce", "Jhin", "Jinx", "K'Sante", 
-            "Kai'Sa", "Kalista", "Karma", "Karthus", "Kassadin", "Katarina", "Kayle", 
-            "Kayn", "Kennen", "Kha'Zix", "Kindred", "Kled", "Kog'Maw", "LeBlanc", 
-            "Lee Sin", "Leona", "Lillia", "Lissandra", "Lucian", "Lulu", "Lux", "Malphite", 
-            "Malzahar", "Maokai", "Master Yi", "Miss Fortune", "Mordekaiser", "Morgana", 
-            "Nami", "Nasus", "Nautilus", "Neeko", "Nidalee", "Nilah", "Nocturne", "Nunu & Willump", 
-            "Olaf", "Orianna", "Ornn", "Pantheon", "Poppy", "Pyke", "Qiyana", "Quinn", 
-            "Rakan", "Rammus", "Rek'Sai", "Rell", "Renata Glasc", "Renekton", "Rengar", 
-            "Riven", "Rumble", "Ryze", "Samira", "Sejuani", "Senna", "Seraphine", "Sett", 
-            "Shaco", "Shen", "Shyvana", "Singed", "Sion", "Sivir", "Skarner", "Sona", 
-            "Soraka", "Swain", "Sylas", "Syndra", "Tahm Kench", "Taliyah", "Talon", 
-            "Taric", "Teemo", "Thresh", "Tristana", "Trundle", "Tryndamere", "Twisted Fate", 
-            "Twitch", "Udyr", "Urgot", "Varus", "Vayne", "Veigar", "Vel'Koz", "Vex", 
-            "Vi", "Viego", "Viktor", "Vladimir", "Volibear", "Warwick", "Wukong", "Xayah", 
-            "Xerath", "Xin Zhao", "Yasuo", "Yone", "Yorick", "Yuumi", "Zac", "Zed", 
-            "Zeri", "Ziggs", "Zilean", "Zoe", "Zyra"
-        ].into_iter().map(|s| s.to_string()).collect();
-        
-        champions.sort();
-        Ok(champions)
-    }
-    
-    pub async fn get_champion_id_by_name(&self, champion_name: &str) -> Result<Option<i64>, LeagueError> {
-        println!("DEBUG: Looking up champion ID for: {}", champion_name);
-        
-        let url = format!("{}/lol-game-data/assets/v1/champions.json", self.base_url);
-        
-        let response = self.client
-            .get(&url)
-            .header("Authorization", &self.auth_header)
-            .send()
-            .await;
-        
-        if let Ok(response) = response {
-            if response.status().is_success() {
-                if let Ok(json) = response.json::<Value>().await {
-                    if let Some(champions) = json.as_object() {
-                        for (_, champion_data) in champions {
-                            if let Some(name) = champion_data.get("name").and_then(|n| n.as_str()) {
-                                if name.to_lowercase() == champion_name.to_lowercase() {
-                                    if let Some(id) = champion_data.get("id").and_then(|i| i.as_i64()) {
-                                        println!("DEBUG: Found champion {} with ID {} from API", name, id);
-                                        return Ok(Some(id));
-                                    }
-                                }
-                            }
-                        }
+        match self.get_champion_summary().await {
+            Ok(summary) if !summary.is_empty() => {
+                let catalog = ChampionCatalog::from_summary(version, locale, &summary, &ChampionDataset::current());
+                debug!("Loaded {} champions from game data (version {})", catalog.champions.len(), catalog.version);
+                
+                if let Some(path) = cache_path.as_deref() {
+                    if let Err(e) = catalog.save(path) {
+                        warn!("Could not cache champion catalog: {}", e);
                     }
                 }
+                Ok(catalog)
+            }
+            result => {
+                if let Err(e) = result {
+                    warn!("Champion game data unavailable: {}", e);
+                }
+                cached.ok_or_else(|| "Champion data unavailable and no cached catalog".into())
             }
         }
-        
-        // Fallback: Use the complete champion ID mapping
-        let champion_id = match champion_name.to_lowercase().as_str() {
-            "annie" => 1,
-            "olaf" => 2,
-            "galio" => 3,
-            "twisted fate" => 4,
-            "xin zhao" => 5,
-            "urgot" => 6,
-            "leblanc" => 7,
-            "vladimir" => 8,
-            "fiddlesticks" => 9,
-            "kayle" => 10,
-            "master yi" => 11,
-            "alistar" => 12,
-            "ryze" => 13,
-            "sion" => 14,
-            "sivir" => 15,
-            "soraka" => 16,
-            "teemo" => 17,
-            "tristana" => 18,
-            "warwick" => 19,
-            "nunu & willump" | "nunu" => 20,
-            "miss fortune" => 21,
-            "ashe" => 22,
-            "tryndamere" => 23,
-            "jax" => 24,
-            "morgana" => 25,
-            "zilean" => 26,
-            "singed" => 27,
-            "evelynn" => 28,
-            "twitch" => 29,
-            "karthus" => 30,
-            "cho'gath" => 31,
-            "amumu" => 32,
-            "rammus" => 33,
-            "anivia" => 34,
-            "shaco" => 35,
-            "dr. mundo" => 36,
-            "sona" => 37,
-            "kassadin" => 38,
-            "irelia" => 39,
-            "janna" => 40,
-            "gangplank" => 41,
-            "corki" => 42,
-            "karma" => 43,
-            "taric" => 44,
-            "veigar" => 45,
-            "trundle" => 48,
-            "swain" => 50,
-            "caitlyn" => 51,
-            "blitzcrank" => 53,
-            "malphite" => 54,
-            "katarina" => 55,
-            "nocturne" => 56,
-            "maokai" => 57,
-            "renekton" => 58,
-            "jarvan iv" => 59,
-            "elise" => 60,
-            "orianna" => 61,
-            "wukong" => 62,
-            "brand" => 63,
-            "lee sin" => 64,
-            "vayne" => 67,
-            "rumble" => 68,
-            "cassiopeia" => 69,
-            "skarner" => 72,
-            "heimerdinger" => 74,
-            "nasus" => 75,
-            "nidalee" => 76,
-            "udyr" => 77,
-            "poppy" => 78,
-            "gragas" => 79,
-            "pantheon" => 80,
-            "ezreal" => 81,
-            "mordekaiser" => 82,
-            "yorick" => 83,
-            "akali" => 84,
-            "kennen" => 85,
-            "garen" => 86,
-            "leona" => 89,
-            "malzahar" => 90,
-            "talon" => 91,
-            "riven" => 92,
-            "kog'maw" => 96,
-            "shen" => 98,
-            "lux" => 99,
-            "xerath" => 101,
-            "shyvana" => 102,
-            "ahri" => 103,
-            "graves" => 104,
-            "fizz" => 105,
-            "volibear" => 106,
-            "rengar" => 107,
-            "varus" => 110,
-            "nautilus" => 111,
-            "viktor" => 112,
-            "sejuani" => 113,
-            "fiora" => 114,
-            "ziggs" => 115,
-            "lulu" => 117,
-            "draven" => 119,
-            "hecarim" => 120,
-            "kha'zix" => 121,
-            "darius" => 122,
-            "jayce" => 126,
-            "lissandra" => 127,
-            "diana" => 131,
-            "quinn" => 133,
-            "syndra" => 134,
-            "aurelion sol" => 136,
-            "kayn" => 141,
-            "zoe" => 142,
-            "zyra" => 143,
-            "kai'sa" => 145,
-            "gnar" => 150,
-            "zac" => 154,
-            "yasuo" => 157,
-            "vel'koz" => 161,
-            "taliyah" => 163,
-            "camille" => 164,
-            "braum" => 201,
-            "jhin" => 202,
-            "kindred" => 203,
-            "jinx" => 222,
-            "tahm kench" => 223,
-            "lucian" => 236,
-            "zed" => 238,
-            "kled" => 240,
-            "ekko" => 245,
-            "vi" => 254,
-            "aatrox" => 266,
-            "nami" => 267,
-            "azir" => 268,
-            "thresh" => 412,
-            "illaoi" => 420,
-            "rek'sai" => 421,
-            "ivern" => 427,
-            "kalista" => 429,
-            "bard" => 432,
-            "rakan" => 497,
-            "xayah" => 498,
-            "ornn" => 516,
-            "pyke" => 555,
-            "neeko" => 518,
-            "sylas" => 517,
-            "senna" => 235,
-            "aphelios" => 523,
-            "sett" => 875,
-            "lillia" => 876,
-            "yone" => 777,
-            "samira" => 360,
-            "seraphine" => 147,
-            "rell" => 526,
-            "viego" => 234,
-            "gwen" => 887,
-            "akshan" => 166,
-            "vex" => 711,
-            "zeri" => 221,
-            "renata glasc" => 888,
-            "bel'veth" => 200,
-            "nilah" => 895,
-            "k'sante" => 897,
-            _ => {
-                println!("DEBUG: Champion {} not found in mapping", champion_name);
-                return Ok(None);
+    }
+    
+    pub async fn get_all_champions(&self) -> Result<Vec<ChampionSummary>, LeagueError> {
+        if let Ok(catalog) = self.champion_catalog().await {
+            if !catalog.champions.is_empty() {
+                return Ok(catalog.summaries());
             }
-        };
+        }
         
-        println!("DEBUG: Found champion {} with ID {} from fallback mapping", champion_name, champion_id);
-        Ok(Some(champion_id))
+        Ok(champion_catalog::fallback_catalog().summaries())
     }
 }
 
+/// How far the monitoring loop got with the current ready check.
+#[derive(Debug, Clone, Copy)]
+enum ReadyCheckState {
+    New,
+    /// Accepting was decided and any countdown is over; retried until it works.
+    Accepting { queue_id: Option<i64>, detected_at: std::time::Instant },
+    /// Accepted, declined, left to the player or cancelled.
+    Handled,
+}
+
 pub struct AutoAcceptService {
     pub client: LeagueClient,
     pub config: ChampSelectConfig,
+    paths: AppPaths,
+    ready_check_control: Arc<ReadyCheckControl>,
+    ready_check: ReadyCheckState,
+    ready_check_failure_reported: bool,
+    post_game_state: PostGameTracker,
+    handled_invitations: HashSet<String>,
+    champ_select: Option<ChampSelectTracker>,
+    counter_picks: CounterPickCache,
+    /// Groups the log lines of the current champion select.
+    champ_select_span: Span,
 }
 
 impl AutoAcceptService {
-    pub async fn new() -> Result<Self, LeagueError> {
-        let client = LeagueClient::new().await?;
-        Ok(AutoAcceptService {
+    pub async fn new(paths: AppPaths) -> Result<Self, LeagueError> {
+        Ok(Self::with_client(LeagueClient::new().await?, paths))
+    }
+    
+    fn with_client(client: LeagueClient, paths: AppPaths) -> Self {
+        AutoAcceptService {
             client,
             config: ChampSelectConfig::default(),
-        })
+            counter_picks: CounterPickCache::new(paths.counter_picks_file()),
+            paths,
+            ready_check_control: Arc::new(ReadyCheckControl::default()),
+            ready_check: ReadyCheckState::New,
+            ready_check_failure_reported: false,
+            post_game_state: PostGameTracker::default(),
+            handled_invitations: HashSet::new(),
+            champ_select: None,
+            champ_select_span: Span::none(),
+        }
+    }
+    
+    pub fn set_ready_check_control(&mut self, control: Arc<ReadyCheckControl>) {
+        self.ready_check_control = control;
     }
     
     pub fn update_config(&mut self, config: ChampSelectConfig) {
-        println!("DEBUG: Updating config: auto_pick={}, auto_ban={}, pick_priority={:?}, ban_priority={:?}", 
+        debug!("Updating config: auto_pick={}, auto_ban={}, pick_priority={:?}, ban_priority={:?}", 
                  config.auto_pick_enabled, config.auto_ban_enabled, config.pick_priority, config.ban_priority);
         self.config = config;
     }
     
-    pub async fn start_monitoring(&mut self, app_handle: tauri::AppHandle) -> Result<(), LeagueError> {
-        println!("DEBUG: Starting monitoring service");
+    pub async fn start_monitoring(&mut self, events: &dyn EventSink) -> Result<(), LeagueError> {
+        info!("Starting monitoring service");
         loop {
             match self.client.is_in_ready_check().await {
                 Ok(true) => {
-                    println!("Ready check detected! Auto-accepting...");
-                    
-                    match self.client.accept_ready_check().await {
-                        Ok(true) => {
-                            println!("Successfully accepted ready check!");
-                            let _ = app_handle.emit("match-accepted", "Match accepted successfully!");
-                        }
-                        Ok(false) => {
-                            println!("Failed to accept ready check");
-                            let _ = app_handle.emit("match-accept-failed", "Failed to accept match");
-                        }
-                        Err(e) => {
-                            println!("Error accepting ready check: {}", e);
-                        }
-                    }
+                    self.handle_ready_check(events).await;
                 }
                 Ok(false) => {
-                    if let Err(e) = self.handle_champion_select(&app_handle).await {
-                        println!("Champion select error: {}", e);
+                    self.ready_check_finished();
+                    if let Err(e) = self.handle_post_game(events).await {
+                        warn!("Post-game error: {}", e);
+                    }
+                    if let Err(e) = self.handle_invitations(events).await {
+                        warn!("Invitation error: {}", e);
+                    }
+                    if let Err(e) = self.handle_champion_select(events).await {
+                        warn!("Champion select error: {}", e);
                     }
                 }
                 Err(e) => {
-                    println!("Error checking ready check status: {}", e);
-                    match LeagueClient::new().await {
-                        Ok(new_client) => {
-                            self.client = new_client;
-                            println!("Reconnected to League Client");
-                        }
-                        Err(_) => {
-                            let _ = app_handle.emit("league-disconnected", "League Client not found");
-                            break;
-                        }
+                    if self.reconnect(events, &e).await != Connection::Restored {
+                        break;
                     }
                 }
             }
@@ -603,182 +811,706 @@ impl AutoAcceptService {
         Ok(())
     }
     
-    pub async fn handle_champion_select(&self, app_handle: &tauri::AppHandle) -> Result<(), LeagueError> {
-        if let Some(session) = self.client.get_champ_select_session().await? {
-            let local_player_cell_id = session.get("localPlayerCellId")
-                .and_then(|id| id.as_i64())
-                .unwrap_or(-1);
+    /// Called when a request to the client fails. Waits for the client,
+    /// retrying with backoff and right away when a new client starts, for up
+    /// to `reconnect.give_up_after_secs`, or until its lockfile has been gone
+    /// for `reconnect.closed_after_secs`. Records and reports why monitoring
+    /// stops if it doesn't come back.
+    pub async fn reconnect(&mut self, events: &dyn EventSink, error: &LeagueError) -> Connection {
+        warn!("Lost the League Client: {}", error);
+        let give_up_after = Duration::from_secs(self.config.reconnect.give_up_after_secs);
+        events.emit("waiting-for-client", json!({
+            "message": "Waiting for the League Client",
+            "give_up_after_secs": give_up_after.as_secs(),
+        }));
+        
+        let closed_after = Duration::from_secs(self.config.reconnect.closed_after_secs);
+        
+        let started = std::time::Instant::now();
+        let mut backoff = Backoff::default();
+        let mut stale_lockfile = LeagueClient::read_lockfile();
+        let mut lockfile_gone_since = stale_lockfile.is_none().then(std::time::Instant::now);
+        let mut attempts = 0;
+        
+        loop {
+            let mut delay = backoff.next_delay(&mut rand::rng());
+            if let Some(gone_since) = lockfile_gone_since {
+                delay = delay.min(closed_after.saturating_sub(gone_since.elapsed()));
+            }
+            LeagueClient::wait_for_new_lockfile(delay, &stale_lockfile).await;
+            attempts += 1;
             
-            if local_player_cell_id == -1 {
-                return Ok(());
+            if let Ok(client) = LeagueClient::new().await {
+                if client.is_in_ready_check().await.is_ok() {
+                    self.client = client;
+                    info!(attempts, "Reconnected to League Client");
+                    events.emit("league-reconnected", "Reconnected to League Client");
+                    return Connection::Restored;
+                }
             }
             
-            println!("DEBUG: In champion select, local player cell ID: {}", local_player_cell_id);
+            stale_lockfile = LeagueClient::read_lockfile();
+            lockfile_gone_since = match stale_lockfile {
+                Some(_) => None,
+                None => Some(lockfile_gone_since.unwrap_or_else(std::time::Instant::now)),
+            };
             
-            if let Some(timer) = session.get("timer") {
-                if let Some(phase) = timer.get("phase") {
-                    println!("DEBUG: Current phase: {}", phase);
+            let closed = lockfile_gone_since.is_some_and(|gone_since| gone_since.elapsed() >= closed_after);
+            if closed || started.elapsed() >= give_up_after {
+                break;
+            }
+            debug!(attempts, "League Client still unavailable");
+        }
+        
+        if !LeagueClient::lockfile_present() {
+            info!("League Client closed, stopping");
+            history::record(&self.paths.history_file(), ActivityRecord::new(ActivityKind::Disconnect, "League Client closed"));
+            events.emit("league-client-closed", "League Client closed");
+            Connection::Closed
+        } else {
+            warn!(attempts, "Giving up on the League Client");
+            history::record(&self.paths.history_file(), ActivityRecord::new(ActivityKind::Disconnect, "League Client not responding"));
+            events.emit("league-disconnected", "League Client not responding");
+            Connection::Lost
+        }
+    }
+    
+    /// Applies the ready-check policy to the current ready check. A failed
+    /// accept or decline is tried again on the next poll. Call
+    /// `ready_check_finished` when the ready check is gone so the next one is handled.
+    pub async fn handle_ready_check(&mut self, events: &dyn EventSink) {
+        match self.ready_check {
+            ReadyCheckState::Handled => {}
+            ReadyCheckState::Accepting { queue_id, detected_at } => self.accept_ready_check(queue_id, detected_at, events).await,
+            ReadyCheckState::New => self.decide_ready_check(events).await,
+        }
+    }
+    
+    async fn decide_ready_check(&mut self, events: &dyn EventSink) {
+        let detected_at = std::time::Instant::now();
+        let queue_id = self.client.get_current_queue_id().await.unwrap_or(None);
+        let now = chrono::Local::now().time();
+        
+        match self.config.ready_check.decide(now, queue_id) {
+            ReadyCheckDecision::Decline => {
+                info!(queue_id = ?queue_id, "Ready check detected, declining (break mode)");
+                let failure = match self.client.decline_ready_check().await {
+                    Ok(WriteOutcome::Done) => {
+                        self.ready_check = ReadyCheckState::Handled;
+                        events.emit("match-declined", "Match declined (break mode)");
+                        history::record(&self.paths.history_file(), ActivityRecord::new(ActivityKind::Decline, "Match declined (break mode)").queue(queue_id));
+                        return;
+                    }
+                    Ok(WriteOutcome::Rejected(reason)) => format!("Failed to decline match: {}", reason),
+                    Err(e) => format!("Error declining match: {}", e),
+                };
+                warn!("{}", failure);
+                if !std::mem::replace(&mut self.ready_check_failure_reported, true) {
+                    events.emit("match-decline-failed", failure);
                 }
-                if let Some(time_left) = timer.get("timeLeftInPhase") {
-                    println!("DEBUG: Time left in phase: {}", time_left);
+            }
+            ReadyCheckDecision::Ignore(reason) => {
+                info!(queue_id = ?queue_id, %reason, "Ready check detected but not accepting");
+                self.ready_check = ReadyCheckState::Handled;
+                events.emit("ready-check-skipped", reason);
+            }
+            ReadyCheckDecision::Accept { delay_secs } => {
+                info!(queue_id = ?queue_id, delay_secs, "Ready check detected, auto-accepting");
+                
+                if delay_secs > 0 && !self.run_accept_countdown(delay_secs, queue_id, events).await {
+                    self.ready_check = ReadyCheckState::Handled;
+                    return;
                 }
+                
+                self.ready_check = ReadyCheckState::Accepting { queue_id, detected_at };
+                self.accept_ready_check(queue_id, detected_at, events).await;
+            }
+        }
+    }
+    
+    /// Accepts the ready check. Failures are reported once per ready check.
+    async fn accept_ready_check(&mut self, queue_id: Option<i64>, detected_at: std::time::Instant, events: &dyn EventSink) {
+        let failure = match self.client.accept_ready_check().await {
+            Ok(WriteOutcome::Done) => {
+                self.ready_check = ReadyCheckState::Handled;
+                info!(queue_id = ?queue_id, latency_ms = detected_at.elapsed().as_millis() as u64, "Accepted ready check");
+                events.emit("match-accepted", "Match accepted successfully!");
+                history::record(&self.paths.history_file(), ActivityRecord::new(ActivityKind::Accept, "Match accepted")
+                    .queue(queue_id)
+                    .accept_latency_ms(detected_at.elapsed().as_millis() as i64));
+                return;
+            }
+            Ok(WriteOutcome::Rejected(reason)) => format!("Failed to accept match: {}", reason),
+            Err(e) => format!("Error accepting match: {}", e),
+        };
+        
+        warn!("{}, retrying", failure);
+        if !std::mem::replace(&mut self.ready_check_failure_reported, true) {
+            events.emit("match-accept-failed", failure.clone());
+            history::record(&self.paths.history_file(), ActivityRecord::new(ActivityKind::AcceptFailed, failure).queue(queue_id));
+        }
+    }
+    
+    pub fn ready_check_finished(&mut self) {
+        self.ready_check = ReadyCheckState::New;
+        self.ready_check_failure_reported = false;
+    }
+    
+    /// Counts down before accepting. Returns `false` if the player cancelled
+    /// or the ready check ended in the meantime.
+    async fn run_accept_countdown(&self, delay_secs: u64, queue_id: Option<i64>, events: &dyn EventSink) -> bool {
+        let control = self.ready_check_control.clone();
+        control.begin_countdown();
+        
+        let mut proceed = true;
+        for seconds_left in (1..=delay_secs).rev() {
+            events.emit("ready-check-countdown", ReadyCheckCountdown { seconds_left, queue_id });
+            sleep(Duration::from_secs(1)).await;
+            
+            if control.is_cancelled() {
+                info!("Pending accept cancelled by user");
+                events.emit("ready-check-cancelled", "Auto-accept cancelled for this match");
+                proceed = false;
+                break;
             }
             
-            if let Some(actions) = session.get("actions").and_then(|a| a.as_array()) {
-                println!("DEBUG: Found {} action groups", actions.len());
-                
-                for (group_index, action_group) in actions.iter().enumerate() {
-                    if let Some(action_array) = action_group.as_array() {
-                        println!("DEBUG: Action group {}: {} actions", group_index, action_array.len());
-                        
-                        for (action_index, action) in action_array.iter().enumerate() {
-                            let actor_cell_id = action.get("actorCellId").and_then(|id| id.as_i64()).unwrap_or(-1);
-                            let action_type = action.get("type").and_then(|t| t.as_str()).unwrap_or("");
-                            let is_in_progress = action.get("isInProgress").and_then(|p| p.as_bool()).unwrap_or(false);
-                            let completed = action.get("completed").and_then(|c| c.as_bool()).unwrap_or(false);
-                            let action_id = action.get("id").and_then(|id| id.as_i64()).unwrap_or(-1);
-                            let champion_id = action.get("championId").and_then(|id| id.as_i64()).unwrap_or(0);
-                            
-                            println!("DEBUG: Action {}.{}: type={}, actor_cell_id={}, is_in_progress={}, completed={}, champion_id={}, action_id={}", 
-                                     group_index, action_index, action_type, actor_cell_id, is_in_progress, completed, champion_id, action_id);
-                            
-                            if actor_cell_id == local_player_cell_id && is_in_progress && !completed && champion_id == 0 {
-                                println!("DEBUG: Found actionable {} for local player", action_type);
-                                
-                                match action_type {
-                                    "ban" if self.config.auto_ban_enabled => {
-                                        println!("DEBUG: Attempting auto-ban");
-                                        if let Err(e) = self.handle_auto_ban(action_id, app_handle).await {
-                                            println!("Auto-ban error: {}", e);
-                                        }
-                                    }
-                                    "pick" if self.config.auto_pick_enabled => {
-                                        println!("DEBUG: Attempting auto-pick");
-                                        if let Err(e) = self.handle_auto_pick(action_id, app_handle).await {
-                                            println!("Auto-pick error: {}", e);
-                                        }
-                                    }
-                                    "ban" => {
-                                        println!("DEBUG: Auto-ban is disabled");
-                                    }
-                                    "pick" => {
-                                        println!("DEBUG: Auto-pick is disabled");
-                                    }
-                                    _ => {
-                                        println!("DEBUG: Unhandled action type: {}", action_type);
-                                    }
-                                }
-                            } else {
-                                if actor_cell_id != local_player_cell_id {
-                                } else if !is_in_progress {
-                                    println!("DEBUG: Action not in progress");
-                                } else if completed {
-                                    println!("DEBUG: Action already completed");
-                                } else if champion_id != 0 {
-                                    println!("DEBUG: Champion already selected (ID: {})", champion_id);
-                                }
-                            }
-                        }
-                    }
+            match self.client.is_in_ready_check().await {
+                Ok(true) => {}
+                Ok(false) => {
+                    info!("Ready check ended during countdown");
+                    proceed = false;
+                    break;
                 }
-            } else {
-                println!("DEBUG: No actions found in session");
+                // A hiccup is not the ready check ending; keep counting down.
+                Err(e) => debug!("Could not check the ready check during countdown: {}", e),
             }
         }
         
+        control.end_countdown();
+        proceed
+    }
+    
+    /// Skips honor, dismisses the stats screen and re-queues once per finished game,
+    /// until the configured session cap is reached. Steps that fail are tried
+    /// again on the next poll.
+    pub async fn handle_post_game(&mut self, events: &dyn EventSink) -> Result<(), LeagueError> {
+        let post_game = self.config.post_game.clone();
+        if !post_game.enabled {
+            return Ok(());
+        }
+        
+        let phase = self.client.get_gameflow_phase().await?;
+        let game_id = if phase == "PreEndOfGame" || phase == "EndOfGame" {
+            let game_id = self.client.get_gameflow_session().await?
+                .and_then(|session| session.pointer("/gameData/gameId").and_then(|id| id.as_i64()));
+            if game_id.is_none() {
+                debug!(%phase, "No game ID in the gameflow session yet");
+                return Ok(());
+            }
+            game_id
+        } else {
+            None
+        };
+        
+        match self.post_game_state.next_step(&post_game, &phase, game_id) {
+            Some(PostGameStep::SkipHonor(game_id)) => {
+                if self.client.skip_honor_vote(game_id).await? {
+                    info!("Skipped honor vote for game {}", game_id);
+                } else {
+                    warn!("Failed to skip honor vote for game {}", game_id);
+                }
+                self.post_game_state.honor_skipped(game_id);
+                Ok(())
+            }
+            Some(PostGameStep::ReturnToLobby(game_id)) => self.return_to_lobby(game_id, &post_game, events).await,
+            Some(PostGameStep::Requeue) => self.requeue(&post_game, events).await,
+            None => Ok(()),
+        }
+    }
+    
+    /// Counts the game, dismisses the stats screen and plays again unless the
+    /// session cap is reached. An error leaves the game to the next poll.
+    async fn return_to_lobby(&mut self, game_id: i64, post_game: &PostGameConfig, events: &dyn EventSink) -> Result<(), LeagueError> {
+        if self.post_game_state.count_game(game_id) {
+            info!("Game {} finished ({} this session)", game_id, self.post_game_state.games_played);
+        }
+        let games_played = self.post_game_state.games_played;
+        let progress = |message: &str| PostGameProgress {
+            message: message.to_string(),
+            games_played,
+            max_games: post_game.max_games,
+        };
+        
+        if post_game.dismiss_stats && !self.client.dismiss_stats().await? {
+            warn!("Failed to dismiss end-of-game stats");
+        }
+        
+        if post_game.session_cap_reached(games_played) {
+            info!("Session cap of {} games reached, not re-queueing", post_game.max_games);
+            self.post_game_state.returned_to_lobby(game_id, false);
+            events.emit("post-game-session-complete", progress("Session game limit reached, not re-queueing"));
+            return Ok(());
+        }
+        
+        if !self.client.play_again().await? {
+            self.post_game_state.returned_to_lobby(game_id, false);
+            events.emit("post-game-failed", progress("Failed to return to the lobby"));
+            return Ok(());
+        }
+        
+        self.post_game_state.returned_to_lobby(game_id, post_game.requeue);
+        if !post_game.requeue {
+            events.emit("post-game-lobby", progress("Returned to the lobby"));
+            return Ok(());
+        }
+        
+        // The lobby needs a moment to be recreated after play-again.
+        sleep(Duration::from_secs(2)).await;
+        self.requeue(post_game, events).await
+    }
+    
+    /// Starts matchmaking from the lobby after a game. An error leaves the
+    /// re-queue pending for the next poll.
+    async fn requeue(&mut self, post_game: &PostGameConfig, events: &dyn EventSink) -> Result<(), LeagueError> {
+        let progress = |message: &str| PostGameProgress {
+            message: message.to_string(),
+            games_played: self.post_game_state.games_played,
+            max_games: post_game.max_games,
+        };
+        
+        if !self.client.is_lobby_leader().await? {
+            events.emit("post-game-lobby", progress("Returned to the lobby (not party leader, waiting for leader to queue)"));
+        } else if self.client.start_matchmaking().await? {
+            events.emit("post-game-requeued", progress("Returned to the lobby and re-queued"));
+        } else {
+            events.emit("post-game-failed", progress("Failed to start matchmaking"));
+        }
+        
+        self.post_game_state.requeue_finished();
         Ok(())
     }
     
-    async fn handle_auto_ban(&self, action_id: i64, app_handle: &tauri::AppHandle) -> Result<(), LeagueError> {
-        println!("DEBUG: Starting auto-ban with action ID: {}", action_id);
-        println!("DEBUG: Ban priority list: {:?}", self.config.ban_priority);
+    /// Accepts pending party invites from whitelisted friends and, if configured,
+    /// declines everyone else. Each invitation is acted on until the client gives
+    /// a definite answer.
+    pub async fn handle_invitations(&mut self, events: &dyn EventSink) -> Result<(), LeagueError> {
+        let invites = self.config.invites.clone();
+        if !invites.auto_accept_enabled {
+            return Ok(());
+        }
         
-        let delay_seconds = rand::rng().random_range(1..=10);
-        println!("DEBUG: Waiting {} seconds before banning...", delay_seconds);
-        let _ = app_handle.emit("auto-action-delay", format!("Waiting {} seconds before banning...", delay_seconds));
-        sleep(Duration::from_secs(delay_seconds)).await;
+        let invitations = self.client.get_received_invitations().await?;
         
-        for (index, champion_name) in self.config.ban_priority.iter().enumerate() {
-            println!("DEBUG: Trying to ban champion {} (priority {})", champion_name, index + 1);
+        for invitation in invitations {
+            let state = invitation.get("state").and_then(|s| s.as_str()).unwrap_or("");
+            let Some(invitation_id) = invitation.get("invitationId").and_then(|id| id.as_str()) else {
+                continue;
+            };
             
-            if let Some(champion_id) = self.client.get_champion_id_by_name(champion_name).await? {
-                println!("DEBUG: Champion {} has ID {}", champion_name, champion_id);
-                
-                match self.client.ban_champion(action_id, champion_id).await {
+            if state != "Pending" || self.handled_invitations.contains(invitation_id) {
+                continue;
+            }
+            
+            let mut inviter = Inviter {
+                summoner_name: invitation.get("fromSummonerName").and_then(|n| n.as_str()).map(|n| n.to_string()),
+                ..Inviter::default()
+            };
+            
+            let mut lookup_failed = false;
+            if let Some(summoner_id) = invitation.get("fromSummonerId").and_then(|id| id.as_i64()) {
+                match self.client.get_summoner_by_id(summoner_id).await {
+                    Ok(summoner) => {
+                        inviter.puuid = summoner.get("puuid").and_then(|p| p.as_str()).map(|p| p.to_string());
+                        inviter.game_name = summoner.get("gameName").and_then(|n| n.as_str()).map(|n| n.to_string());
+                        inviter.tag_line = summoner.get("tagLine").and_then(|t| t.as_str()).map(|t| t.to_string());
+                    }
+                    Err(e) => {
+                        debug!("Could not look up the sender of invitation {}: {}", invitation_id, e);
+                        lookup_failed = true;
+                    }
+                }
+            }
+            
+            let event = InviteEvent {
+                inviter: inviter.display_name(),
+                invitation_id: invitation_id.to_string(),
+            };
+            
+            // Only a definite answer marks the invitation handled; errors are
+            // tried again on the next poll.
+            let handled = if invites.is_whitelisted(&inviter) {
+                info!("Accepting party invite from {}", event.inviter);
+                match self.client.accept_invitation(invitation_id).await {
                     Ok(true) => {
-                        println!("Successfully banned {}", champion_name);
-                        let _ = app_handle.emit("champion-banned", format!("Banned {}", champion_name));
-                        return Ok(());
+                        events.emit("party-invite-accepted", event);
+                        true
                     }
                     Ok(false) => {
-                        println!("Failed to ban {} (might be already banned or unavailable)", champion_name);
-                        continue;
+                        events.emit("party-invite-failed", event);
+                        true
                     }
                     Err(e) => {
-                        println!("Error banning {}: {}", champion_name, e);
-                        continue;
+                        warn!("Error accepting party invite from {}: {}", event.inviter, e);
+                        false
+                    }
+                }
+            } else if lookup_failed {
+                // The Riot ID or PUUID might be on the whitelist.
+                false
+            } else if invites.decline_others {
+                info!("Declining party invite from {}", event.inviter);
+                match self.client.decline_invitation(invitation_id).await {
+                    Ok(declined) => {
+                        if declined {
+                            events.emit("party-invite-declined", event);
+                        }
+                        true
+                    }
+                    Err(e) => {
+                        warn!("Error declining party invite from {}: {}", event.inviter, e);
+                        false
                     }
                 }
             } else {
-                println!("DEBUG: Could not find champion ID for {}", champion_name);
+                debug!("Ignoring party invite from {} (not whitelisted)", event.inviter);
+                true
+            };
+            
+            if handled {
+                self.handled_invitations.insert(invitation_id.to_string());
             }
         }
         
-        println!("DEBUG: No champions from ban list were available to ban");
-        let _ = app_handle.emit("champion-ban-failed", "No champions from ban list available");
         Ok(())
     }
     
-    async fn handle_auto_pick(&self, action_id: i64, app_handle: &tauri::AppHandle) -> Result<(), LeagueError> {
-        println!("DEBUG: Starting auto-pick with action ID: {}", action_id);
-        println!("DEBUG: Pick priority list: {:?}", self.config.pick_priority);
+    pub async fn handle_champion_select(&mut self, events: &dyn EventSink) -> Result<(), LeagueError> {
+        let Some(session) = self.client.get_champ_select_session().await? else {
+            let span = self.champ_select_span.clone();
+            self.finish_champ_select(events).instrument(span).await;
+            return Ok(());
+        };
         
-        let delay_seconds = rand::rng().random_range(1..=10);
-        println!("DEBUG: Waiting {} seconds before picking...", delay_seconds);
-        let _ = app_handle.emit("auto-action-delay", format!("Waiting {} seconds before picking...", delay_seconds));
-        sleep(Duration::from_secs(delay_seconds)).await;
+        if !session.is_player() {
+            return Ok(());
+        }
         
-        let available_champions = self.client.get_available_champions().await?;
-        println!("DEBUG: Found {} available champions", available_champions.len());
+        if self.champ_select.is_none() {
+            let queue_id = self.client.get_current_queue_id().await.unwrap_or(None);
+            self.champ_select_span = info_span!("champ_select", game_id = session.game_id, queue_id = ?queue_id, cell_id = session.local_player_cell_id);
+            self.champ_select_span.in_scope(|| info!("Champion select started"));
+            self.champ_select = Some(ChampSelectTracker { queue_id, ..ChampSelectTracker::default() });
+        }
+        
+        let span = self.champ_select_span.clone();
+        self.process_champion_select(&session, events).instrument(span).await
+    }
+    
+    async fn process_champion_select(&mut self, session: &ChampSelectSession, events: &dyn EventSink) -> Result<(), LeagueError> {
+        trace!(phase = %session.timer.phase, time_left_ms = session.timer.time_left_ms(), "Champion select timer");
+        if let Some(tracker) = self.champ_select.as_mut() {
+            tracker.timer_phase = session.timer.phase.clone();
+        }
         
-        for (index, champion_name) in self.config.pick_priority.iter().enumerate() {
-            println!("DEBUG: Trying to pick champion {} (priority {})", champion_name, index + 1);
+        for action in session.my_actions() {
+            trace!(?action, "Local player action");
             
-            if let Some(champion_id) = self.client.get_champion_id_by_name(champion_name).await? {
-                println!("DEBUG: Champion {} has ID {}", champion_name, champion_id);
-                
-                let is_owned = available_champions.iter().any(|champ| {
-                    champ.get("id").and_then(|id| id.as_i64()) == Some(champion_id)
-                });
-                
-                println!("DEBUG: Champion {} is owned: {}", champion_name, is_owned);
-                
-                if is_owned {
-                    match self.client.pick_champion(action_id, champion_id).await {
-                        Ok(true) => {
-                            println!("Successfully picked {}", champion_name);
-                            let _ = app_handle.emit("champion-picked", format!("Picked {}", champion_name));
-                            return Ok(());
-                        }
-                        Ok(false) => {
-                            println!("Failed to pick {} (might be banned/picked by someone else)", champion_name);
-                            continue;
-                        }
-                        Err(e) => {
-                            println!("Error picking {}: {}", champion_name, e);
-                            continue;
-                        }
-                    }
-                } else {
-                    println!("DEBUG: Champion {} is not owned, skipping", champion_name);
+            if action.action_type == ActionType::Pick {
+                if let Some(tracker) = self.champ_select.as_mut() {
+                    tracker.has_pick_action = true;
+                    tracker.locked_in |= action.completed;
                 }
-            } else {
-                println!("DEBUG: Could not find champion ID for {}", champion_name);
             }
         }
         
-        println!("DEBUG: No champions from pick list were available to pick");
-        let _ = app_handle.emit("champion-pick-failed", "No champions from pick list available");
+        if !session.my_actions().any(|action| action.is_actionable()) {
+            return Ok(());
+        }
+        
+        let pickable = self.pickable_champion_ids().await?;
+        let fallback = champion_catalog::fallback_catalog();
+        let catalog = match self.client.champion_catalog().await {
+            Ok(catalog) if !catalog.champions.is_empty() => catalog,
+            _ => &*fallback,
+        };
+        let counter_picks = self.counter_picks.table();
+        
+        for planned in draft::decide(session, &self.config, catalog, &pickable, counter_picks) {
+            debug!(?planned, "Planned champion select action");
+            self.execute(planned, events).await;
+        }
+        
         Ok(())
     }
-}
\ No newline at end of file
+    
+    /// Champions the client will let us pick right now, or every owned
+    /// champion when the pickable list is unavailable.
+    async fn pickable_champion_ids(&self) -> Result<HashSet<i64>, LeagueError> {
+        match self.client.get_pickable_champion_ids().await {
+            Ok(ids) => Ok(ids.into_iter().collect()),
+            Err(e) => {
+                debug!("{}, falling back to owned champions", e);
+                Ok(self.client.get_available_champions().await?
+                    .iter()
+                    .filter_map(|champ| champ.get("id").and_then(|id| id.as_i64()))
+                    .collect())
+            }
+        }
+    }
+    
+    fn champ_select_queue_id(&self) -> Option<i64> {
+        self.champ_select.as_ref().and_then(|tracker| tracker.queue_id)
+    }
+    
+    /// Champion select ended. If the game did not start, report it as a dodge.
+    async fn finish_champ_select(&mut self, events: &dyn EventSink) {
+        if self.champ_select.is_none() {
+            return;
+        }
+        
+        // The tracker stays until the phase is known, so an error is retried
+        // on the next poll instead of losing the dodge.
+        let phase = match self.client.get_gameflow_phase().await {
+            Ok(phase) => phase,
+            Err(e) => {
+                warn!("Could not check gameflow phase after champion select: {}", e);
+                return;
+            }
+        };
+        let Some(tracker) = self.champ_select.take() else {
+            return;
+        };
+        self.champ_select_span = Span::none();
+        
+        if let Some(reason) = tracker.dodge_reason(&phase) {
+            info!(%reason, locked_in = tracker.locked_in, "Champion select ended without the game starting");
+            history::record(&self.paths.history_file(), ActivityRecord::new(ActivityKind::Dodge, reason.clone()).queue(tracker.queue_id));
+            events.emit("champ-select-dodged", DodgeRecord {
+                timestamp: chrono::Local::now().to_rfc3339(),
+                reason,
+                queue_id: tracker.queue_id,
+                locked_in: tracker.locked_in,
+            });
+        }
+    }
+    
+    /// Carries out one planned action: waits the humanizing delay for regular
+    /// bans and picks, then tries the candidates in order.
+    async fn execute(&self, planned: PlannedAction, events: &dyn EventSink) {
+        match planned {
+            PlannedAction::Ban { action_id, candidates } => {
+                self.humanizing_delay("banning", events).await;
+                self.execute_ban(action_id, &candidates, events).await;
+            }
+            PlannedAction::Pick { action_id, candidates, counter_rule } => {
+                if let Some(rule) = &counter_rule {
+                    info!("Counter-pick rule matched: enemy {} -> prefer {:?}", rule.enemy, rule.prefer);
+                }
+                self.humanizing_delay("picking", events).await;
+                self.execute_pick(action_id, &candidates, counter_rule, events).await;
+            }
+            PlannedAction::LastResortPick { action_id, candidates } => {
+                info!("Pick timer almost out, attempting last-resort pick");
+                self.execute_last_resort_pick(action_id, &candidates, events).await;
+            }
+        }
+    }
+    
+    async fn humanizing_delay(&self, doing: &str, events: &dyn EventSink) {
+        let delay_seconds = rand::rng().random_range(1..=10);
+        debug!("Waiting {} seconds before {}...", delay_seconds, doing);
+        events.emit("auto-action-delay", format!("Waiting {} seconds before {}...", delay_seconds, doing));
+        sleep(Duration::from_secs(delay_seconds)).await;
+    }
+    
+    async fn execute_ban(&self, action_id: i64, candidates: &[Candidate], events: &dyn EventSink) {
+        let mut last_rejection = None;
+        for candidate in candidates {
+            debug!(champion = %candidate.name, champion_id = candidate.champion_id, slot = ?candidate.slot, "Trying to ban champion");
+            
+            match self.client.ban_champion(action_id, candidate.champion_id).await {
+                Ok(WriteOutcome::Done) => {
+                    info!(champion = %candidate.name, champion_id = candidate.champion_id, slot = ?candidate.slot, "Banned champion");
+                    let message = format!("Banned {}", candidate.name);
+                    history::record(&self.paths.history_file(), ActivityRecord::new(ActivityKind::Ban, message.clone())
+                        .queue(self.champ_select_queue_id())
+                        .champion(candidate.champion_id, &candidate.name)
+                        .priority_slot(candidate.slot));
+                    events.emit("champion-banned", message);
+                    return;
+                }
+                Ok(WriteOutcome::Rejected(reason)) => {
+                    warn!(champion = %candidate.name, champion_id = candidate.champion_id, "Could not ban champion: {}", reason);
+                    last_rejection = Some(reason);
+                }
+                Err(e) => {
+                    warn!(champion = %candidate.name, champion_id = candidate.champion_id, error = %e, "Error banning champion");
+                }
+            }
+        }
+        
+        let message = with_last_rejection("No champions from ban list available", last_rejection);
+        warn!("{}", message);
+        history::record(&self.paths.history_file(), ActivityRecord::new(ActivityKind::BanFailed, message.clone()).queue(self.champ_select_queue_id()));
+        events.emit("champion-ban-failed", message);
+    }
+    
+    async fn execute_pick(&self, action_id: i64, candidates: &[Candidate], counter_rule: Option<CounterPickRule>, events: &dyn EventSink) {
+        let mut last_rejection = None;
+        for candidate in candidates {
+            debug!(champion = %candidate.name, champion_id = candidate.champion_id, slot = ?candidate.slot, "Trying to pick champion");
+            let record = |kind: ActivityKind, message: String| {
+                ActivityRecord::new(kind, message)
+                    .queue(self.champ_select_queue_id())
+                    .champion(candidate.champion_id, &candidate.name)
+                    .priority_slot(candidate.slot)
+            };
+            
+            match self.client.pick_champion(action_id, candidate.champion_id).await {
+                Ok(WriteOutcome::Done) => {
+                    info!(champion = %candidate.name, champion_id = candidate.champion_id, slot = ?candidate.slot, "Picked champion");
+                    let message = match &counter_rule {
+                        Some(rule) => format!("Picked {} (countering {})", candidate.name, rule.enemy),
+                        None => format!("Picked {}", candidate.name),
+                    };
+                    history::record(&self.paths.history_file(), record(ActivityKind::Pick, message.clone()));
+                    events.emit("champion-picked", ChampionPickedPayload {
+                        message,
+                        champion: candidate.name.clone(),
+                        champion_id: candidate.champion_id,
+                        counter_rule,
+                    });
+                    return;
+                }
+                Ok(WriteOutcome::Rejected(reason)) => {
+                    warn!(champion = %candidate.name, champion_id = candidate.champion_id, "Could not pick champion: {}", reason);
+                    history::record(&self.paths.history_file(), record(ActivityKind::PickFailed, format!("Could not pick {}: {}", candidate.name, reason)));
+                    last_rejection = Some(reason);
+                }
+                Err(e) => {
+                    warn!(champion = %candidate.name, champion_id = candidate.champion_id, error = %e, "Error picking champion");
+                    history::record(&self.paths.history_file(), record(ActivityKind::PickFailed, format!("Error picking {}: {}", candidate.name, e)));
+                }
+            }
+        }
+        
+        let message = with_last_rejection("No champions from pick list available", last_rejection);
+        warn!("{}", message);
+        history::record(&self.paths.history_file(), ActivityRecord::new(ActivityKind::PickFailed, message.clone()).queue(self.champ_select_queue_id()));
+        events.emit("champion-pick-failed", message);
+    }
+    
+    /// The pick timer is about to run out and nothing is locked in: lock in the
+    /// hovered champion or anything pickable from the configured pool.
+    async fn execute_last_resort_pick(&self, action_id: i64, candidates: &[Candidate], events: &dyn EventSink) {
+        let mut last_rejection = None;
+        for candidate in candidates {
+            match self.client.pick_champion(action_id, candidate.champion_id).await {
+                Ok(WriteOutcome::Done) => {
+                    info!(champion = %candidate.name, champion_id = candidate.champion_id, "Last-resort pick locked in");
+                    let message = format!("Timer almost out, locked in {}", candidate.name);
+                    history::record(&self.paths.history_file(), ActivityRecord::new(ActivityKind::Pick, message.clone())
+                        .queue(self.champ_select_queue_id())
+                        .champion(candidate.champion_id, &candidate.name));
+                    events.emit("champion-last-resort-picked", message);
+                    return;
+                }
+                Ok(WriteOutcome::Rejected(reason)) => {
+                    warn!(champion = %candidate.name, champion_id = candidate.champion_id, "Could not lock in champion: {}", reason);
+                    last_rejection = Some(reason);
+                }
+                Err(e) => warn!("Error locking in {}: {}", candidate.name, e),
+            }
+        }
+        
+        let message = with_last_rejection("Last-resort pick failed: no pickable champion could be locked in", last_rejection);
+        history::record(&self.paths.history_file(), ActivityRecord::new(ActivityKind::PickFailed, message.clone()).queue(self.champ_select_queue_id()));
+        events.emit("champion-pick-failed", message);
+    }
+}
+
+/// `message`, followed by why the client refused the last attempt, if it did.
+fn with_last_rejection(message: &str, last_rejection: Option<String>) -> String {
+    match last_rejection {
+        Some(reason) => format!("{} (last refusal: {})", message, reason),
+        None => message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stand_in::StandIn;
+    use std::sync::Mutex;
+
+    /// The names of the events sent, in order.
+    #[derive(Default)]
+    struct RecordedEvents(Mutex<Vec<String>>);
+
+    impl EventSink for RecordedEvents {
+        fn send(&self, event: &str, _payload: Value) {
+            self.0.lock().unwrap().push(event.to_string());
+        }
+    }
+
+    /// Answers champ select actions as `stand_in` does.
+    async fn client(stand_in: StandIn) -> (StandIn, LeagueClient) {
+        let stand_in = stand_in.with_error_body(r#"{"message":"Champion is not available"}"#);
+        let base_url = stand_in.serve("/lol-champ-select/v1/session/actions/:id").await;
+        (stand_in, LeagueClient::for_stand_in(base_url))
+    }
+
+    #[tokio::test]
+    async fn lock_in_survives_a_momentary_server_error() {
+        let (stand_in, client) = client(StandIn::new(&[500])).await;
+
+        assert_eq!(client.pick_champion(1, 222).await.unwrap(), WriteOutcome::Done);
+        assert_eq!(stand_in.requests(), 2);
+    }
+
+    #[tokio::test]
+    async fn rejected_picks_are_not_retried() {
+        let (stand_in, client) = client(StandIn::new(&[400])).await;
+
+        assert_eq!(
+            client.pick_champion(1, 222).await.unwrap(),
+            WriteOutcome::Rejected(r#"PATCH /lol-champ-select/v1/session/actions/1 failed with 400 Bad Request: {"message":"Champion is not available"}"#.to_string())
+        );
+        assert_eq!(stand_in.requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn persistent_server_errors_include_the_response_body() {
+        let (stand_in, client) = client(StandIn::new(&[500, 502, 503])).await;
+
+        let error = client.ban_champion(1, 157).await.unwrap_err().to_string();
+        assert_eq!(error, r#"PATCH /lol-champ-select/v1/session/actions/1 failed with 503 Service Unavailable: {"message":"Champion is not available"}"#);
+        assert_eq!(stand_in.requests(), 3);
+    }
+
+    #[tokio::test]
+    async fn timed_out_writes_are_not_retried() {
+        let (stand_in, client) = client(StandIn::new(&[]).with_delay(Duration::from_secs(2))).await;
+
+        assert!(client.pick_champion(1, 222).await.is_err());
+        assert_eq!(stand_in.requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn a_closed_client_stops_monitoring_without_waiting_out_give_up_after() {
+        assert!(!LeagueClient::lockfile_present(), "a League Client is running");
+        let dir = std::env::temp_dir().join(format!("league-auto-accept-reconnect-{}", std::process::id()));
+        let paths = AppPaths { config_dir: dir.clone(), data_dir: dir.clone(), cache_dir: dir.clone(), log_dir: dir.clone() };
+        let mut service = AutoAcceptService::with_client(LeagueClient::for_stand_in("http://127.0.0.1:9".to_string()), paths);
+        service.config.reconnect = ReconnectConfig { give_up_after_secs: 3600, closed_after_secs: 1 };
+        let events = RecordedEvents::default();
+
+        let connection = tokio::time::timeout(Duration::from_secs(10), service.reconnect(&events, &"connection refused".into()))
+            .await
+            .expect("still waiting for the closed client");
+        assert_eq!(connection, Connection::Closed);
+        assert_eq!(*events.0.lock().unwrap(), vec!["waiting-for-client", "league-client-closed"]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
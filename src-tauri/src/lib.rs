@@ -0,0 +1,32 @@
+//! Everything except the Tauri commands and window setup, shared by the
+//! desktop app (`main.rs`) and the headless CLI (`bin/league-auto-accept-cli.rs`).
+//! Code that needs Tauri is behind the `desktop` feature, so the CLI can be
+//! built without it.
+
+pub mod champ_select;
+pub mod champion_catalog;
+pub mod config_store;
+pub mod config_validation;
+pub mod control_api;
+pub mod counter_picks;
+pub mod dodge;
+pub mod draft;
+pub mod events;
+pub mod history;
+pub mod hotkeys;
+pub mod invites;
+pub mod league_client;
+pub mod lobby;
+pub mod logging;
+pub mod notifications;
+pub mod paths;
+pub mod post_game;
+pub mod ready_check;
+pub mod reconnect;
+pub mod request_policy;
+pub mod secret;
+#[cfg(test)]
+mod stand_in;
+#[cfg(feature = "desktop")]
+pub mod tray;
+pub mod webhooks;
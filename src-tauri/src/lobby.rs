@@ -0,0 +1,196 @@
+use crate::league_client::{LeagueClient, LeagueError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::time::{sleep, Duration, Instant};
+use tracing::{info, warn};
+
+/// Saved "one click to queue" setup.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LobbyConfig {
+    pub queue_id: i64,
+    /// Client position names: TOP, JUNGLE, MIDDLE, BOTTOM, UTILITY or FILL. Empty to skip.
+    #[serde(default)]
+    pub first_position: String,
+    #[serde(default)]
+    pub second_position: String,
+    /// Friends to invite, by name, Riot ID (`Name#TAG`) or PUUID.
+    #[serde(default)]
+    pub invitees: Vec<String>,
+    /// How long to wait for invited friends to join before searching anyway.
+    #[serde(default = "default_invite_wait_secs")]
+    pub invite_wait_secs: u64,
+}
+
+fn default_invite_wait_secs() -> u64 {
+    60
+}
+
+impl Default for LobbyConfig {
+    fn default() -> Self {
+        Self {
+            queue_id: 420,
+            first_position: String::new(),
+            second_position: String::new(),
+            invitees: Vec::new(),
+            invite_wait_secs: default_invite_wait_secs(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct InviteReport {
+    pub invited: Vec<String>,
+    pub not_found: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueUpReport {
+    pub queue_id: i64,
+    pub invites: InviteReport,
+    pub searching: bool,
+}
+
+fn find_friend<'a>(friends: &'a [Value], entry: &str) -> Option<&'a Value> {
+    let entry = entry.trim();
+    friends.iter().find(|friend| {
+        let field = |key: &str| friend.get(key).and_then(|v| v.as_str()).unwrap_or("");
+        let riot_id = format!("{}#{}", field("gameName"), field("gameTag"));
+
+        [field("name"), field("gameName"), riot_id.as_str(), field("puuid")]
+            .iter()
+            .any(|candidate| !candidate.is_empty() && candidate.eq_ignore_ascii_case(entry))
+    })
+}
+
+/// Invites friends from the friend list by name, Riot ID or PUUID.
+pub async fn invite_friends(client: &LeagueClient, names: &[String]) -> Result<InviteReport, LeagueError> {
+    let mut report = InviteReport::default();
+    if names.is_empty() {
+        return Ok(report);
+    }
+
+    let friends = client.get_friends().await?;
+    let mut summoner_ids = Vec::new();
+
+    for name in names {
+        match find_friend(&friends, name).and_then(|f| f.get("summonerId").and_then(|id| id.as_i64())) {
+            Some(summoner_id) => {
+                summoner_ids.push(summoner_id);
+                report.invited.push(name.clone());
+            }
+            None => report.not_found.push(name.clone()),
+        }
+    }
+
+    if !summoner_ids.is_empty() && !client.invite_summoners(&summoner_ids).await? {
+        return Err("Failed to send lobby invitations".into());
+    }
+
+    Ok(report)
+}
+
+/// Polls the lobby until it has `expected` members or `timeout` passes.
+async fn wait_for_members(client: &LeagueClient, expected: usize, timeout: Duration) -> Result<(), LeagueError> {
+    let deadline = Instant::now() + timeout;
+
+    while Instant::now() < deadline {
+        let members = client.get_lobby().await?
+            .and_then(|lobby| lobby.get("members").and_then(|m| m.as_array()).map(|m| m.len()))
+            .unwrap_or(0);
+
+        if members >= expected {
+            return Ok(());
+        }
+        sleep(Duration::from_secs(2)).await;
+    }
+
+    info!("Not every invited friend joined within {}s, searching anyway", timeout.as_secs());
+    Ok(())
+}
+
+/// Creates the lobby, sets positions, invites the saved friends and starts matchmaking.
+pub async fn queue_up(client: &LeagueClient, lobby: &LobbyConfig) -> Result<QueueUpReport, LeagueError> {
+    if !client.create_lobby(lobby.queue_id).await? {
+        return Err(format!("Failed to create lobby for queue {}", lobby.queue_id).into());
+    }
+
+    if !lobby.first_position.is_empty()
+        && !client.set_position_preferences(&lobby.first_position, &lobby.second_position).await?
+    {
+        warn!("Queue {} did not accept position preferences", lobby.queue_id);
+    }
+
+    let invites = invite_friends(client, &lobby.invitees).await?;
+
+    if !invites.invited.is_empty() {
+        wait_for_members(client, 1 + invites.invited.len(), Duration::from_secs(lobby.invite_wait_secs)).await?;
+    }
+
+    let searching = client.start_matchmaking().await?;
+
+    Ok(QueueUpReport {
+        queue_id: lobby.queue_id,
+        invites,
+        searching,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stand_in::StandIn;
+    use serde_json::json;
+
+    fn friends() -> Vec<Value> {
+        vec![
+            json!({ "name": "", "gameName": "Faker", "gameTag": "KR1", "puuid": "puuid-faker", "summonerId": 1 }),
+            json!({ "name": "Old Name", "gameName": "Caps", "gameTag": "", "puuid": "puuid-caps", "summonerId": 2 }),
+        ]
+    }
+
+    fn summoner_id(entry: &str) -> Option<i64> {
+        find_friend(&friends(), entry).and_then(|friend| friend["summonerId"].as_i64())
+    }
+
+    #[test]
+    fn friends_match_by_name_riot_id_or_puuid_ignoring_case() {
+        assert_eq!(summoner_id("faker"), Some(1));
+        assert_eq!(summoner_id(" FAKER#kr1 "), Some(1));
+        assert_eq!(summoner_id("puuid-faker"), Some(1));
+        assert_eq!(summoner_id("old name"), Some(2));
+        assert_eq!(summoner_id("Faker#EUW"), None);
+    }
+
+    #[test]
+    fn friends_without_a_tag_match_by_game_name() {
+        assert_eq!(summoner_id("Caps"), Some(2));
+        assert_eq!(summoner_id("Caps#EUW"), None);
+    }
+
+    #[test]
+    fn blank_entries_match_nobody() {
+        assert_eq!(summoner_id(""), None);
+        assert_eq!(summoner_id("   "), None);
+    }
+
+    #[tokio::test]
+    async fn queue_up_creates_the_lobby_then_searches() {
+        let stand_in = StandIn::new(&[]);
+        let client = LeagueClient::for_stand_in(stand_in.serve("/*path").await);
+
+        let report = queue_up(&client, &LobbyConfig::default()).await.unwrap();
+        assert!(report.searching);
+        assert_eq!(stand_in.requested(), vec!["POST /lol-lobby/v2/lobby", "POST /lol-lobby/v2/lobby/matchmaking/search"]);
+    }
+
+    #[tokio::test]
+    async fn queue_up_stops_when_the_lobby_is_rejected() {
+        let stand_in = StandIn::new(&[400]);
+        let client = LeagueClient::for_stand_in(stand_in.serve("/*path").await);
+        let lobby = LobbyConfig { first_position: "MIDDLE".to_string(), invitees: vec!["Faker".to_string()], ..LobbyConfig::default() };
+
+        let error = queue_up(&client, &lobby).await.unwrap_err();
+        assert_eq!(error.to_string(), "Failed to create lobby for queue 420");
+        assert_eq!(stand_in.requested(), vec!["POST /lol-lobby/v2/lobby"]);
+    }
+}
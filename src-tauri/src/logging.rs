@@ -0,0 +1,142 @@
+use crate::league_client::LeagueError;
+use crate::secret;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+pub const LOG_FILE_PREFIX: &str = "league-auto-accept";
+pub const LOG_FILE_SUFFIX: &str = "log";
+/// Daily files kept before the oldest is deleted.
+pub const MAX_LOG_FILES: usize = 7;
+pub const DEFAULT_LEVEL: &str = "info";
+
+static FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+static LOG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Our own modules log at `level`; dependencies (reqwest, hyper, tauri) only warnings.
+fn filter_for(level: &str) -> Result<EnvFilter, LeagueError> {
+    let level = level.trim().to_ascii_lowercase();
+    if !["error", "warn", "info", "debug", "trace"].contains(&level.as_str()) {
+        return Err(format!("Unknown log level '{}'", level).into());
+    }
+
+    EnvFilter::try_new(format!("warn,{}={}", env!("CARGO_CRATE_NAME"), level))
+        .map_err(|e| format!("Invalid log filter: {}", e).into())
+}
+
+/// Installs the global subscriber: a daily-rotated file in `log_dir`, plus
+/// stderr in debug builds, leaving stdout to the CLI's output. Safe to call
+/// once; later calls are ignored.
+pub fn init(log_dir: &Path, level: &str) -> Result<(), LeagueError> {
+    if FILTER.get().is_some() {
+        return Ok(());
+    }
+
+    fs::create_dir_all(log_dir)?;
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix(LOG_FILE_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(log_dir)
+        .map_err(|e| format!("Could not open log file in {}: {}", log_dir.display(), e))?;
+
+    let filter = filter_for(level).or_else(|_| filter_for(DEFAULT_LEVEL))?;
+    let (filter, handle) = reload::Layer::new(filter);
+
+    let file_layer = fmt::layer().with_ansi(false).with_writer(appender);
+    let stderr_layer = cfg!(debug_assertions).then(|| fmt::layer().with_writer(std::io::stderr));
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(file_layer)
+        .with(stderr_layer)
+        .try_init()
+        .map_err(|e| format!("Could not install logger: {}", e))?;
+
+    let _ = FILTER.set(handle);
+    let _ = LOG_DIR.set(log_dir.to_path_buf());
+    Ok(())
+}
+
+/// Changes the log level without restarting.
+pub fn set_level(level: &str) -> Result<(), LeagueError> {
+    let filter = filter_for(level)?;
+    let handle = FILTER.get().ok_or("Logging is not initialized")?;
+    handle
+        .reload(filter)
+        .map_err(|e| format!("Could not change log level: {}", e).into())
+}
+
+/// The last `count` lines across the rotated log files, oldest first, with
+/// credentials redacted so they are safe to paste into a bug report.
+pub fn tail(count: usize) -> Result<Vec<String>, LeagueError> {
+    let dir = LOG_DIR.get().ok_or("Logging is not initialized")?;
+    tail_dir(dir, count)
+}
+
+fn tail_dir(dir: &Path, count: usize) -> Result<Vec<String>, LeagueError> {
+    // Rotated files are named `<prefix>.<yyyy-mm-dd>.<suffix>`, so sorting by
+    // name sorts by date.
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(LOG_FILE_PREFIX) && name.ends_with(LOG_FILE_SUFFIX))
+        })
+        .collect();
+    files.sort();
+
+    let mut lines: Vec<String> = Vec::new();
+    for path in files.iter().rev() {
+        if lines.len() >= count {
+            break;
+        }
+        let content = fs::read_to_string(path)?;
+        let mut older: Vec<String> = content.lines().map(secret::redact).collect();
+        older.append(&mut lines);
+        lines = older;
+    }
+
+    let excess = lines.len().saturating_sub(count);
+    lines.drain(..excess);
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secret::Secret;
+
+    #[test]
+    fn exported_lines_never_contain_the_auth_header() {
+        let dir = std::env::temp_dir().join(format!("league-auto-accept-logs-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let header = Secret::new("Basic cmlvdDpzM2NyZXQtcmVtb3RpbmctdG9rZW4=");
+        fs::write(
+            dir.join(format!("{}.2026-10-17.{}", LOG_FILE_PREFIX, LOG_FILE_SUFFIX)),
+            format!("older line\nrequest failed with Authorization: {}\n", header.expose()),
+        )
+        .unwrap();
+        fs::write(
+            dir.join(format!("{}.2026-10-18.{}", LOG_FILE_PREFIX, LOG_FILE_SUFFIX)),
+            "newest line\n",
+        )
+        .unwrap();
+
+        let lines = tail_dir(&dir, 2).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("request failed"));
+        assert_eq!(lines[1], "newest line");
+        assert!(lines.iter().all(|line| !line.contains(header.expose())));
+        assert!(lines.iter().all(|line| !line.contains("cmlvdDpzM2NyZXQ")));
+    }
+}
@@ -1,27 +1,71 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-mod league_client;
-
-use league_client::{AutoAcceptService, LeagueClient, ChampSelectConfig};
+use league_auto_accept::{champion_catalog, config_store, config_validation, history, lobby, logging};
+use league_auto_accept::dodge::DodgeProtectionConfig;
+use league_auto_accept::history::{ActivityRecord, HistoryFilter, HistoryStats};
+use league_auto_accept::hotkeys::{HotkeyAction, HotkeyConfig, Hotkeys};
+use league_auto_accept::champion_catalog::ChampionSummary;
+use league_auto_accept::config_validation::{ChampionEntry, ValidationReport};
+use league_auto_accept::control_api::{self, ControlApiConfig, ControlFuture, ControlHandler, ControlRequest};
+use league_auto_accept::events::{EventBus, EventSink};
+use std::collections::HashSet;
+use league_auto_accept::invites::InviteConfig;
+use league_auto_accept::league_client::{AutoAcceptService, LeagueClient, ChampSelectConfig, Connection, WriteOutcome};
+use league_auto_accept::lobby::{InviteReport, LobbyConfig};
+use league_auto_accept::notifications::{NotificationConfig, Notifications};
+use league_auto_accept::paths::AppPaths;
+use league_auto_accept::post_game::PostGameConfig;
+use league_auto_accept::ready_check::{ReadyCheckControl, ReadyCheckPolicy};
+use league_auto_accept::secret::Secret;
+use league_auto_accept::tray::{TrayMenu, TrayStatus, TRAY_ID};
+use league_auto_accept::webhooks::{WebhookConfig, Webhooks};
+use serde_json::{json, Value};
 use std::sync::{Arc, Mutex};
 use tauri::{Emitter, State, Manager, LogicalPosition, LogicalSize};
-use tauri::menu::{Menu, MenuItem};
 use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri_plugin_autostart::{MacosLauncher, ManagerExt};
+use tauri_plugin_global_shortcut::ShortcutState;
+use tracing::{debug, info, trace, warn};
 
-type ServiceState = Arc<Mutex<Option<tauri::async_runtime::JoinHandle<()>>>>; 
+type ServiceState = Arc<Mutex<ServiceSlot>>;
 type ConfigState = Arc<Mutex<ChampSelectConfig>>;
+type ReadyCheckState = Arc<ReadyCheckControl>;
+
+/// Passed when the app is launched at login, to start in the tray.
+const MINIMIZED_ARG: &str = "--minimized";
+
+/// How often the client watcher looks for the League client.
+const CLIENT_WATCH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// The monitoring loop. Held as `Starting` while connecting to the client so
+/// a second start in the meantime is refused.
+#[derive(Default)]
+enum ServiceSlot {
+    #[default]
+    Stopped,
+    Starting,
+    Running(tauri::async_runtime::JoinHandle<()>),
+}
+
+impl ServiceSlot {
+    fn is_active(&self) -> bool {
+        !matches!(self, ServiceSlot::Stopped)
+    }
+}
+
+/// The running control API server, if enabled.
+#[derive(Default)]
+struct ControlApiServer(Mutex<Option<tauri::async_runtime::JoinHandle<()>>>);
 
 #[tauri::command]
-async fn connect_to_league() -> Result<String, String> {
+async fn connect_to_league(app_handle: tauri::AppHandle) -> Result<String, String> {
     match LeagueClient::new().await {
         Ok(client) => {
-            match client.get_summoner_info().await {
-                Ok(summoner_info) => {
-                    let display_name = summoner_info
-                        .get("displayName")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("Unknown");
-                    Ok(format!("Connected to League Client. Summoner: {}", display_name))
+            match client.get_summoner_name().await {
+                Ok(name) => {
+                    let message = format!("Connected to League Client. Summoner: {}", name);
+                    update_tray(&app_handle, |status| status.connected_as = Some(name));
+                    Ok(message)
                 }
                 Err(e) => Err(format!("Connected to League Client but failed to get summoner info: {}", e))
             }
@@ -30,40 +74,193 @@ async fn connect_to_league() -> Result<String, String> {
     }
 }
 
+/// Redraws the tray after `change`, once the tray exists.
+fn update_tray(app_handle: &tauri::AppHandle, change: impl FnOnce(&mut TrayStatus)) {
+    if let Some(tray) = app_handle.try_state::<TrayMenu>() {
+        tray.update(app_handle, change);
+    }
+}
+
+/// Shows the pick and ban toggles in the tray and the window. Call without
+/// holding the config lock.
+fn sync_toggles(app_handle: &tauri::AppHandle, config: &ChampSelectConfig) {
+    update_tray(app_handle, |status| {
+        status.auto_pick = config.auto_pick_enabled;
+        status.auto_ban = config.auto_ban_enabled;
+    });
+    emit_service_state(app_handle);
+}
+
+/// Tells the window whether auto-accept runs and which toggles are on, so it
+/// follows changes made from the tray, a shortcut or the control API.
+fn emit_service_state(app_handle: &tauri::AppHandle) {
+    let running = app_handle.state::<ServiceState>().lock().unwrap().is_active();
+    let (auto_pick, auto_ban) = {
+        let config = app_handle.state::<ConfigState>();
+        let config = config.lock().unwrap();
+        (config.auto_pick_enabled, config.auto_ban_enabled)
+    };
+    
+    let events: &dyn EventSink = app_handle;
+    events.emit("auto-accept-state", json!({ "running": running, "auto_pick": auto_pick, "auto_ban": auto_ban }));
+}
+
+fn save_config(app_handle: &tauri::AppHandle, config: &ChampSelectConfig) -> Result<(), String> {
+    let path = AppPaths::from_app(app_handle).map_err(|e| e.to_string())?.config_file();
+    config_store::save(&path, config).map_err(|e| format!("Failed to save configuration: {}", e))
+}
+
 #[tauri::command]
 async fn update_champ_select_config(
     config_state: State<'_, ConfigState>,
+    app_handle: tauri::AppHandle,
     auto_pick_enabled: bool,
     auto_ban_enabled: bool,
-    pick_priority: Vec<String>,
-    ban_priority: Vec<String>,
-) -> Result<String, String> {
-    println!("DEBUG: Updating config - auto_pick: {}, auto_ban: {}, pick_priority: {:?}, ban_priority: {:?}", 
+    pick_priority: Vec<ChampionEntry>,
+    ban_priority: Vec<ChampionEntry>,
+) -> Result<ValidationReport, String> {
+    debug!("Updating config - auto_pick: {}, auto_ban: {}, pick_priority: {:?}, ban_priority: {:?}", 
              auto_pick_enabled, auto_ban_enabled, pick_priority, ban_priority);
     
-    let mut config = config_state.lock().unwrap();
-    config.auto_pick_enabled = auto_pick_enabled;
-    config.auto_ban_enabled = auto_ban_enabled;
-    config.pick_priority = pick_priority;
-    config.ban_priority = ban_priority;
+    let mut report = priority_list_report(&pick_priority, &ban_priority).await;
+    
+    if report.has_errors() {
+        info!("Config not saved, priority lists contain unknown champions");
+        return Ok(report);
+    }
+    
+    let config = {
+        let mut config = config_state.lock().unwrap();
+        config.auto_pick_enabled = auto_pick_enabled;
+        config.auto_ban_enabled = auto_ban_enabled;
+        config.pick_priority = config_validation::champion_ids(&report.pick_priority);
+        config.ban_priority = config_validation::champion_ids(&report.ban_priority);
+        config.legacy_unresolved.clear();
+        config.clone()
+    };
+    save_config(&app_handle, &config)?;
+    sync_toggles(&app_handle, &config);
+    report.saved = true;
+    
+    info!("Config updated successfully");
+    Ok(report)
+}
+
+#[tauri::command]
+async fn update_ready_check_policy(
+    config_state: State<'_, ConfigState>,
+    app_handle: tauri::AppHandle,
+    policy: ReadyCheckPolicy,
+) -> Result<String, String> {
+    policy.validate()?;
+    
+    let config = {
+        let mut config = config_state.lock().unwrap();
+        config.ready_check = policy;
+        config.clone()
+    };
+    save_config(&app_handle, &config)?;
     
-    println!("DEBUG: Config updated successfully");
-    Ok("Configuration updated successfully".to_string())
+    Ok("Ready check policy updated successfully".to_string())
+}
+
+#[tauri::command]
+async fn update_post_game_config(
+    config_state: State<'_, ConfigState>,
+    app_handle: tauri::AppHandle,
+    post_game: PostGameConfig,
+) -> Result<String, String> {
+    let config = {
+        let mut config = config_state.lock().unwrap();
+        config.post_game = post_game;
+        config.clone()
+    };
+    save_config(&app_handle, &config)?;
+    
+    Ok("Post-game settings updated successfully".to_string())
+}
+
+#[tauri::command]
+async fn update_invite_config(
+    config_state: State<'_, ConfigState>,
+    app_handle: tauri::AppHandle,
+    invites: InviteConfig,
+) -> Result<String, String> {
+    let config = {
+        let mut config = config_state.lock().unwrap();
+        config.invites = invites;
+        config.clone()
+    };
+    save_config(&app_handle, &config)?;
+    
+    Ok("Invite settings updated successfully".to_string())
+}
+
+#[tauri::command]
+async fn update_dodge_protection_config(
+    config_state: State<'_, ConfigState>,
+    app_handle: tauri::AppHandle,
+    dodge_protection: DodgeProtectionConfig,
+) -> Result<String, String> {
+    let config = {
+        let mut config = config_state.lock().unwrap();
+        config.dodge_protection = dodge_protection;
+        config.clone()
+    };
+    save_config(&app_handle, &config)?;
+    
+    Ok("Dodge protection settings updated successfully".to_string())
+}
+
+#[tauri::command]
+async fn set_log_level(
+    config_state: State<'_, ConfigState>,
+    app_handle: tauri::AppHandle,
+    level: String,
+) -> Result<String, String> {
+    logging::set_level(&level).map_err(|e| e.to_string())?;
+    
+    let config = {
+        let mut config = config_state.lock().unwrap();
+        config.app.log_level = level.trim().to_ascii_lowercase();
+        config.clone()
+    };
+    save_config(&app_handle, &config)?;
+    
+    info!(level = %config.app.log_level, "Log level changed");
+    Ok(format!("Log level set to {}", config.app.log_level))
+}
+
+/// The last `lines` log lines, for attaching to bug reports.
+#[tauri::command]
+async fn export_logs(lines: Option<usize>) -> Result<String, String> {
+    logging::tail(lines.unwrap_or(500))
+        .map(|lines| lines.join("\n"))
+        .map_err(|e| format!("Failed to read logs: {}", e))
+}
+
+#[tauri::command]
+async fn cancel_pending_accept(ready_check_state: State<'_, ReadyCheckState>) -> Result<String, String> {
+    if ready_check_state.request_cancel() {
+        Ok("Pending accept cancelled".to_string())
+    } else {
+        Err("No pending accept to cancel".to_string())
+    }
 }
 
 #[tauri::command]
 async fn get_champ_select_config(config_state: State<'_, ConfigState>) -> Result<ChampSelectConfig, String> {
     let config = config_state.lock().unwrap();
-    println!("DEBUG: Retrieved config - auto_pick: {}, auto_ban: {}, pick_priority: {:?}, ban_priority: {:?}", 
+    trace!("Retrieved config - auto_pick: {}, auto_ban: {}, pick_priority: {:?}, ban_priority: {:?}", 
              config.auto_pick_enabled, config.auto_ban_enabled, config.pick_priority, config.ban_priority);
     Ok(config.clone())
 }
 
 #[tauri::command]
-async fn get_all_champions() -> Result<Vec<String>, String> {
+async fn get_all_champions() -> Result<Vec<ChampionSummary>, String> {
     match LeagueClient::new().await {
         Ok(client) => {
-            match client.get_all_champion_names().await {
+            match client.get_all_champions().await {
                 Ok(champions) => Ok(champions),
                 Err(e) => Err(format!("Failed to get champions: {}", e))
             }
@@ -72,35 +269,110 @@ async fn get_all_champions() -> Result<Vec<String>, String> {
     }
 }
 
+/// Validates priority lists against the client's catalog, checking that picks
+/// are owned, or against the bundled catalog when the client isn't running.
+async fn priority_list_report(pick_priority: &[ChampionEntry], ban_priority: &[ChampionEntry]) -> ValidationReport {
+    let client = LeagueClient::new().await.ok();
+    let fallback = champion_catalog::fallback_catalog();
+    let catalog = match &client {
+        Some(client) => client.champion_catalog().await.unwrap_or(&fallback),
+        None => &fallback,
+    };
+    
+    let owned: Option<HashSet<i64>> = match &client {
+        Some(client) => client.get_available_champions().await.ok().map(|champions| {
+            champions.iter().filter_map(|champ| champ.get("id").and_then(|id| id.as_i64())).collect()
+        }),
+        None => None,
+    };
+    
+    ValidationReport::new(pick_priority, ban_priority, catalog, owned.as_ref())
+}
+
+/// Checks priority lists the way saving does, without saving.
 #[tauri::command]
-async fn start_auto_accept(
-    service_state: State<'_, ServiceState>,
-    config_state: State<'_, ConfigState>,
-    app_handle: tauri::AppHandle,
-) -> Result<String, String> {
+async fn validate_priority_lists(pick_priority: Vec<ChampionEntry>, ban_priority: Vec<ChampionEntry>) -> Result<ValidationReport, String> {
+    Ok(priority_list_report(&pick_priority, &ban_priority).await)
+}
+
+#[tauri::command]
+async fn get_activity_history(app_handle: tauri::AppHandle, filter: Option<HistoryFilter>) -> Result<Vec<ActivityRecord>, String> {
+    let path = AppPaths::from_app(&app_handle).map_err(|e| e.to_string())?.history_file();
+    history::load(&path, &filter.unwrap_or_default())
+        .map_err(|e| format!("Failed to read activity history: {}", e))
+}
+
+#[tauri::command]
+async fn get_activity_stats(app_handle: tauri::AppHandle, filter: Option<HistoryFilter>) -> Result<HistoryStats, String> {
+    let path = AppPaths::from_app(&app_handle).map_err(|e| e.to_string())?.history_file();
+    let records = history::load(&path, &filter.unwrap_or_default())
+        .map_err(|e| format!("Failed to read activity history: {}", e))?;
+    Ok(HistoryStats::from_records(&records))
+}
+
+#[tauri::command]
+async fn refresh_champion_dataset(path: String) -> Result<String, String> {
+    match champion_catalog::refresh_dataset_from_file(std::path::Path::new(&path)) {
+        Ok(dataset) => Ok(format!("Champion dataset updated to version {} ({} champions)", dataset.version, dataset.champions.len())),
+        Err(e) => Err(format!("Failed to refresh champion dataset: {}", e))
+    }
+}
+
+#[tauri::command]
+async fn start_auto_accept(app_handle: tauri::AppHandle) -> Result<String, String> {
+    start_service(app_handle).await
+}
+
+/// Starts the monitoring loop. Shared by the command and the control API.
+async fn start_service(app_handle: tauri::AppHandle) -> Result<String, String> {
+    let service_state = app_handle.state::<ServiceState>().inner().clone();
+    
     {
-        let current_service = service_state.lock().unwrap();
-        if current_service.is_some() {
+        let mut current_service = service_state.lock().unwrap();
+        if current_service.is_active() {
             return Err("Auto-accept is already running".to_string());
         }
+        *current_service = ServiceSlot::Starting;
+    }
+    
+    let started = connect_service(app_handle, &service_state).await;
+    if started.is_err() {
+        let mut current_service = service_state.lock().unwrap();
+        if matches!(*current_service, ServiceSlot::Starting) {
+            *current_service = ServiceSlot::Stopped;
+        }
     }
+    started
+}
+
+/// Connects to the client and spawns the monitoring loop into the slot
+/// `start_service` reserved.
+async fn connect_service(app_handle: tauri::AppHandle, service_state: &ServiceState) -> Result<String, String> {
+    let config_state = app_handle.state::<ConfigState>().inner().clone();
+    let ready_check_control = app_handle.state::<ReadyCheckState>().inner().clone();
     
     let config = {
         let config_guard = config_state.lock().unwrap();
         let config = config_guard.clone();
-        println!("DEBUG: Starting service with config - auto_pick: {}, auto_ban: {}", 
+        info!("Starting service with config - auto_pick: {}, auto_ban: {}", 
                  config.auto_pick_enabled, config.auto_ban_enabled);
         config
     };
     
-    match AutoAcceptService::new().await {
+    let paths = AppPaths::from_app(&app_handle).map_err(|e| e.to_string())?;
+    
+    match AutoAcceptService::new(paths).await {
         Ok(mut service) => {
+            let summoner = service.client.get_summoner_name().await.ok();
             service.update_config(config);
+            service.set_ready_check_control(ready_check_control);
             
-            let service_state_clone = service_state.inner().clone();
-            let config_state_clone = config_state.inner().clone();
+            let service_state_clone = service_state.clone();
+            let config_state_clone = config_state.clone();
+            let tray_handle = app_handle.clone();
             
             let handle = tauri::async_runtime::spawn(async move {
+                let events: &dyn EventSink = &app_handle;
                 let mut last_config: Option<ChampSelectConfig> = None;
                 
                 loop {
@@ -121,38 +393,28 @@ async fn start_auto_accept(
                     
                     match service.client.is_in_ready_check().await {
                         Ok(true) => {
-                            println!("Ready check detected! Auto-accepting...");
-                            
-                            match service.client.accept_ready_check().await {
-                                Ok(true) => {
-                                    println!("Successfully accepted ready check!");
-                                    let _ = app_handle.emit("match-accepted", "Match accepted successfully!");
-                                }
-                                Ok(false) => {
-                                    println!("Failed to accept ready check");
-                                    let _ = app_handle.emit("match-accept-failed", "Failed to accept match");
-                                }
-                                Err(e) => {
-                                    println!("Error accepting ready check: {}", e);
-                                }
-                            }
+                            service.handle_ready_check(events).await;
                         }
                         Ok(false) => {
-                            if let Err(e) = service.handle_champion_select(&app_handle).await {
-                                println!("Champion select error: {}", e);
+                            service.ready_check_finished();
+                            if let Err(e) = service.handle_post_game(events).await {
+                                warn!("Post-game error: {}", e);
+                            }
+                            if let Err(e) = service.handle_invitations(events).await {
+                                warn!("Invitation error: {}", e);
+                            }
+                            if let Err(e) = service.handle_champion_select(events).await {
+                                warn!("Champion select error: {}", e);
                             }
                         }
                         Err(e) => {
-                            println!("Error checking ready check status: {}", e);
-                            match LeagueClient::new().await {
-                                Ok(new_client) => {
-                                    service.client = new_client;
-                                    println!("Reconnected to League Client");
-                                }
-                                Err(_) => {
-                                    let _ = app_handle.emit("league-disconnected", "League Client not found");
-                                    break;
-                                }
+                            if service.reconnect(events, &e).await != Connection::Restored {
+                                update_tray(&app_handle, |status| {
+                                    status.connected_as = None;
+                                    status.waiting_for_client = false;
+                                    status.auto_accept = false;
+                                });
+                                break;
                             }
                         }
                     }
@@ -162,14 +424,26 @@ async fn start_auto_accept(
                 
                 {
                     let mut current_service = service_state_clone.lock().unwrap();
-                    *current_service = None;
+                    if matches!(*current_service, ServiceSlot::Running(_)) {
+                        *current_service = ServiceSlot::Stopped;
+                    }
                 }
+                emit_service_state(&app_handle);
             });
             
             {
                 let mut current_service = service_state.lock().unwrap();
-                *current_service = Some(handle);
+                if !matches!(*current_service, ServiceSlot::Starting) {
+                    handle.abort();
+                    return Err("Auto-accept was stopped while starting".to_string());
+                }
+                *current_service = ServiceSlot::Running(handle);
             }
+            update_tray(&tray_handle, |status| {
+                status.connected_as = summoner.or(status.connected_as.take());
+                status.auto_accept = true;
+            });
+            emit_service_state(&tray_handle);
             
             Ok("Auto-accept started successfully".to_string())
         }
@@ -178,21 +452,216 @@ async fn start_auto_accept(
 }
 
 #[tauri::command]
-async fn stop_auto_accept(service_state: State<'_, ServiceState>) -> Result<String, String> {
-    let mut current_service = service_state.lock().unwrap();
+async fn stop_auto_accept(app_handle: tauri::AppHandle) -> Result<String, String> {
+    stop_service(&app_handle)
+}
+
+fn stop_service(app_handle: &tauri::AppHandle) -> Result<String, String> {
+    let previous = std::mem::take(&mut *app_handle.state::<ServiceState>().lock().unwrap());
     
-    if let Some(handle) = current_service.take() {
-        handle.abort();
+    if previous.is_active() {
+        if let ServiceSlot::Running(handle) = previous {
+            handle.abort();
+        }
+        update_tray(app_handle, |status| {
+            status.waiting_for_client = false;
+            status.auto_accept = false;
+        });
+        emit_service_state(app_handle);
         Ok("Auto-accept stopped successfully".to_string())
     } else {
         Err("Auto-accept is not running".to_string())
     }
 }
 
+/// Stops auto-accept if it is running, starts it otherwise.
+async fn toggle_service(app_handle: tauri::AppHandle) -> Result<String, String> {
+    let running = app_handle.state::<ServiceState>().lock().unwrap().is_active();
+    if running {
+        stop_service(&app_handle)
+    } else {
+        start_service(app_handle).await
+    }
+}
+
+/// Starts auto-accept when the League client starts, if `start_with_client`
+/// is on. Only the client appearing triggers it, so stopping auto-accept by
+/// hand while the client runs sticks. When the client exits, the service
+/// stops once the client has been gone for `reconnect.closed_after_secs`,
+/// and this starts it again with the next client.
+fn spawn_client_watcher(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut client_was_running = false;
+        
+        loop {
+            let client_running = LeagueClient::lockfile_present();
+            let enabled = app_handle.state::<ConfigState>().lock().unwrap().start_with_client;
+            let service_running = app_handle.state::<ServiceState>().lock().unwrap().is_active();
+            
+            let mut started = true;
+            if client_running && !client_was_running && enabled && !service_running {
+                info!("League Client started, starting auto-accept");
+                if let Err(e) = start_service(app_handle.clone()).await {
+                    warn!("Could not start auto-accept for the new client: {}", e);
+                    started = false;
+                }
+            }
+            // Try again on the next tick if starting failed.
+            client_was_running = client_running && started;
+            
+            tokio::time::sleep(CLIENT_WATCH_INTERVAL).await;
+        }
+    });
+}
+
+#[tauri::command]
+async fn set_start_with_client(
+    config_state: State<'_, ConfigState>,
+    app_handle: tauri::AppHandle,
+    enabled: bool,
+) -> Result<String, String> {
+    let config = {
+        let mut config = config_state.lock().unwrap();
+        config.start_with_client = enabled;
+        config.clone()
+    };
+    save_config(&app_handle, &config)?;
+    
+    Ok("Start with client setting updated successfully".to_string())
+}
+
+#[tauri::command]
+async fn set_autostart(app_handle: tauri::AppHandle, enabled: bool) -> Result<bool, String> {
+    let autolaunch = app_handle.autolaunch();
+    let result = if enabled { autolaunch.enable() } else { autolaunch.disable() };
+    result.map_err(|e| format!("Failed to update launch at login: {}", e))?;
+    
+    autolaunch.is_enabled().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn is_autostart_enabled(app_handle: tauri::AppHandle) -> Result<bool, String> {
+    app_handle.autolaunch().is_enabled().map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn is_auto_accept_running(service_state: State<'_, ServiceState>) -> Result<bool, String> {
     let current_service = service_state.lock().unwrap();
-    Ok(current_service.is_some())
+    Ok(current_service.is_active())
+}
+
+#[tauri::command]
+async fn update_control_api_config(
+    config_state: State<'_, ConfigState>,
+    app_handle: tauri::AppHandle,
+    mut control_api: ControlApiConfig,
+) -> Result<ControlApiConfig, String> {
+    // An empty token asks for a new one.
+    if control_api.enabled && control_api.token.is_empty() {
+        control_api.token = control_api::generate_token();
+    }
+    control_api.validate()?;
+    restart_control_api(&app_handle, &control_api).await?;
+    
+    let config = {
+        let mut config = config_state.lock().unwrap();
+        config.control_api = control_api.clone();
+        config.clone()
+    };
+    save_config(&app_handle, &config)?;
+    
+    Ok(control_api)
+}
+
+/// Stops the control API if it is running, then starts it with `config` if enabled.
+async fn restart_control_api(app_handle: &tauri::AppHandle, config: &ControlApiConfig) -> Result<(), String> {
+    let running = app_handle.state::<ControlApiServer>().0.lock().unwrap().take();
+    if let Some(handle) = running {
+        handle.abort();
+        // Wait for the listener to close so the port can be bound again.
+        let _ = handle.await;
+    }
+    
+    if !config.enabled {
+        return Ok(());
+    }
+    
+    let listener = control_api::bind(config).await.map_err(|e| e.to_string())?;
+    let handler = Arc::new(AppControl(app_handle.clone()));
+    let events = app_handle.state::<EventBus>().inner().clone();
+    let server = control_api::serve(listener, Secret::new(config.token.clone()), handler, events);
+    *app_handle.state::<ControlApiServer>().0.lock().unwrap() = Some(tauri::async_runtime::spawn(server));
+    
+    Ok(())
+}
+
+/// Carries out control API requests the same way as the matching commands.
+struct AppControl(tauri::AppHandle);
+
+impl ControlHandler for AppControl {
+    fn handle(&self, request: ControlRequest) -> ControlFuture<'_> {
+        Box::pin(async move {
+            let app_handle = &self.0;
+            let message = |message: String| json!({ "message": message });
+            
+            match request {
+                ControlRequest::Status => {
+                    let running = app_handle.state::<ServiceState>().lock().unwrap().is_active();
+                    Ok(json!({ "running": running }))
+                }
+                ControlRequest::Start => start_service(app_handle.clone()).await.map(message),
+                ControlRequest::Stop => stop_service(app_handle).map(message),
+                ControlRequest::GetConfig => {
+                    let config = app_handle.state::<ConfigState>().lock().unwrap().clone();
+                    serde_json::to_value(config).map_err(|e| e.to_string())
+                }
+                ControlRequest::SetConfig(changes) => apply_config_changes(app_handle, changes).await
+                    .and_then(|config| serde_json::to_value(config).map_err(|e| e.to_string())),
+                ControlRequest::ManualAccept => manual_accept().await.map(message),
+            }
+        })
+    }
+}
+
+/// Merges a partial config into the current one, then validates, applies and
+/// saves it. Champion lists may use names or IDs.
+async fn apply_config_changes(app_handle: &tauri::AppHandle, changes: Value) -> Result<ChampSelectConfig, String> {
+    if !changes.is_object() {
+        return Err("Expected a JSON object of settings to change".to_string());
+    }
+    if changes.get("control_api").is_some() {
+        return Err("Control API settings can only be changed in the app".to_string());
+    }
+    
+    let catalog = champion_catalog::live_or_fallback().await;
+    let config_state = app_handle.state::<ConfigState>();
+    let mut config = config_state.lock().unwrap();
+    
+    let mut value = serde_json::to_value(&*config).map_err(|e| e.to_string())?;
+    config_store::merge(&mut value, changes);
+    config_validation::resolve_champion_lists(&mut value, &catalog)?;
+    
+    let updated: ChampSelectConfig = serde_json::from_value(value).map_err(|e| format!("Invalid config: {}", e))?;
+    updated.ready_check.validate()?;
+    for webhook in &updated.webhooks {
+        webhook.validate()?;
+    }
+    if updated.app.log_level != config.app.log_level {
+        logging::set_level(&updated.app.log_level).map_err(|e| e.to_string())?;
+    }
+    if updated.hotkeys != config.hotkeys {
+        app_handle.state::<Hotkeys>().apply(app_handle, &updated.hotkeys)?;
+    }
+    
+    save_config(app_handle, &updated)?;
+    app_handle.state::<Webhooks>().set(updated.webhooks.clone());
+    app_handle.state::<Notifications>().set(updated.notifications.clone());
+    *config = updated.clone();
+    drop(config);
+    sync_toggles(app_handle, &updated);
+    
+    info!("Config updated through the control API");
+    Ok(updated)
 }
 
 #[tauri::command]
@@ -200,8 +669,8 @@ async fn manual_accept() -> Result<String, String> {
     match LeagueClient::new().await {
         Ok(client) => {
             match client.accept_ready_check().await {
-                Ok(true) => Ok("Match accepted successfully!".to_string()),
-                Ok(false) => Err("Failed to accept match (no ready check active?)".to_string()),
+                Ok(WriteOutcome::Done) => Ok("Match accepted successfully!".to_string()),
+                Ok(WriteOutcome::Rejected(reason)) => Err(format!("Failed to accept match (no ready check active?): {}", reason)),
                 Err(e) => Err(format!("Error accepting match: {}", e))
             }
         }
@@ -209,6 +678,182 @@ async fn manual_accept() -> Result<String, String> {
     }
 }
 
+#[tauri::command]
+async fn update_lobby_config(
+    config_state: State<'_, ConfigState>,
+    app_handle: tauri::AppHandle,
+    lobby: LobbyConfig,
+) -> Result<String, String> {
+    let config = {
+        let mut config = config_state.lock().unwrap();
+        config.lobby = lobby;
+        config.clone()
+    };
+    save_config(&app_handle, &config)?;
+    
+    Ok("Lobby settings updated successfully".to_string())
+}
+
+#[tauri::command]
+async fn update_webhooks(
+    config_state: State<'_, ConfigState>,
+    webhooks: State<'_, Webhooks>,
+    app_handle: tauri::AppHandle,
+    hooks: Vec<WebhookConfig>,
+) -> Result<String, String> {
+    for hook in &hooks {
+        hook.validate()?;
+    }
+    
+    let config = {
+        let mut config = config_state.lock().unwrap();
+        config.webhooks = hooks.clone();
+        config.clone()
+    };
+    save_config(&app_handle, &config)?;
+    webhooks.set(hooks);
+    
+    Ok("Webhooks updated successfully".to_string())
+}
+
+#[tauri::command]
+async fn update_notification_config(
+    config_state: State<'_, ConfigState>,
+    notifications: State<'_, Notifications>,
+    app_handle: tauri::AppHandle,
+    notification_config: NotificationConfig,
+) -> Result<String, String> {
+    let config = {
+        let mut config = config_state.lock().unwrap();
+        config.notifications = notification_config.clone();
+        config.clone()
+    };
+    save_config(&app_handle, &config)?;
+    notifications.set(notification_config);
+    
+    Ok("Notification settings updated successfully".to_string())
+}
+
+#[tauri::command]
+async fn update_hotkeys(
+    config_state: State<'_, ConfigState>,
+    hotkeys: State<'_, Hotkeys>,
+    app_handle: tauri::AppHandle,
+    hotkey_config: HotkeyConfig,
+) -> Result<String, String> {
+    hotkeys.apply(&app_handle, &hotkey_config)?;
+    
+    let config = {
+        let mut config = config_state.lock().unwrap();
+        config.hotkeys = hotkey_config;
+        config.clone()
+    };
+    save_config(&app_handle, &config)?;
+    
+    Ok("Shortcuts updated successfully".to_string())
+}
+
+/// Runs a global shortcut's action, the same way as the matching command.
+fn run_hotkey(app_handle: tauri::AppHandle, action: HotkeyAction) {
+    tauri::async_runtime::spawn(async move {
+        let result = match action {
+            HotkeyAction::ToggleAutoAccept => toggle_service(app_handle).await,
+            HotkeyAction::ManualAccept => manual_accept().await,
+        };
+        match result {
+            Ok(message) => info!(action = action.label(), "{}", message),
+            Err(e) => warn!(action = action.label(), "{}", e),
+        }
+    });
+}
+
+#[tauri::command]
+async fn send_test_webhook(webhooks: State<'_, Webhooks>, hook: WebhookConfig) -> Result<String, String> {
+    webhooks.send_test(&hook).await.map_err(|e| e.to_string())?;
+    Ok("Test notification sent".to_string())
+}
+
+#[tauri::command]
+async fn create_lobby(queue_id: i64) -> Result<String, String> {
+    match LeagueClient::new().await {
+        Ok(client) => {
+            match client.create_lobby(queue_id).await {
+                Ok(true) => Ok(format!("Created lobby for queue {}", queue_id)),
+                Ok(false) => Err(format!("Failed to create lobby for queue {}", queue_id)),
+                Err(e) => Err(format!("Error creating lobby: {}", e))
+            }
+        }
+        Err(e) => Err(format!("Failed to connect to League Client: {}", e))
+    }
+}
+
+#[tauri::command]
+async fn set_position_preferences(first_position: String, second_position: String) -> Result<String, String> {
+    match LeagueClient::new().await {
+        Ok(client) => {
+            match client.set_position_preferences(&first_position, &second_position).await {
+                Ok(true) => Ok(format!("Positions set to {} / {}", first_position, second_position)),
+                Ok(false) => Err("Failed to set positions (not in a lobby with role selection?)".to_string()),
+                Err(e) => Err(format!("Error setting positions: {}", e))
+            }
+        }
+        Err(e) => Err(format!("Failed to connect to League Client: {}", e))
+    }
+}
+
+#[tauri::command]
+async fn invite_friends(
+    config_state: State<'_, ConfigState>,
+    names: Option<Vec<String>>,
+) -> Result<InviteReport, String> {
+    let names = names.unwrap_or_else(|| config_state.lock().unwrap().lobby.invitees.clone());
+    
+    match LeagueClient::new().await {
+        Ok(client) => lobby::invite_friends(&client, &names).await
+            .map_err(|e| format!("Error inviting friends: {}", e)),
+        Err(e) => Err(format!("Failed to connect to League Client: {}", e))
+    }
+}
+
+#[tauri::command]
+async fn start_matchmaking() -> Result<String, String> {
+    match LeagueClient::new().await {
+        Ok(client) => {
+            match client.start_matchmaking().await {
+                Ok(true) => Ok("Matchmaking started".to_string()),
+                Ok(false) => Err("Failed to start matchmaking (not in a lobby or not party leader?)".to_string()),
+                Err(e) => Err(format!("Error starting matchmaking: {}", e))
+            }
+        }
+        Err(e) => Err(format!("Failed to connect to League Client: {}", e))
+    }
+}
+
+async fn run_quick_queue(lobby_config: LobbyConfig) -> Result<String, String> {
+    let client = LeagueClient::new().await
+        .map_err(|e| format!("Failed to connect to League Client: {}", e))?;
+    
+    let report = lobby::queue_up(&client, &lobby_config).await
+        .map_err(|e| format!("Quick queue failed: {}", e))?;
+    
+    let mut message = if report.searching {
+        format!("In queue {}", report.queue_id)
+    } else {
+        format!("Lobby for queue {} created but matchmaking did not start", report.queue_id)
+    };
+    if !report.invites.not_found.is_empty() {
+        message.push_str(&format!(" (friends not found: {})", report.invites.not_found.join(", ")));
+    }
+    
+    Ok(message)
+}
+
+#[tauri::command]
+async fn quick_queue(config_state: State<'_, ConfigState>) -> Result<String, String> {
+    let lobby_config = config_state.lock().unwrap().lobby.clone();
+    run_quick_queue(lobby_config).await
+}
+
 #[tauri::command]
 async fn show_window(app_handle: tauri::AppHandle) -> Result<(), tauri::Error> {
     if let Some(window) = app_handle.get_webview_window("main") {
@@ -242,29 +887,118 @@ async fn hide_window(app_handle: tauri::AppHandle) -> Result<(), tauri::Error> {
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
-        .manage(ServiceState::new(Mutex::new(None)))
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_autostart::init(MacosLauncher::LaunchAgent, Some(vec![MINIMIZED_ARG])))
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(|app, shortcut, event| {
+                    if event.state() != ShortcutState::Pressed {
+                        return;
+                    }
+                    if let Some(action) = app.state::<Hotkeys>().action_for(shortcut) {
+                        run_hotkey(app.clone(), action);
+                    }
+                })
+                .build(),
+        )
+        .manage(ServiceState::default())
         .manage(ConfigState::new(Mutex::new(ChampSelectConfig::default())))
+        .manage(ReadyCheckState::new(ReadyCheckControl::default()))
+        .manage(EventBus::default())
+        .manage(ControlApiServer::default())
+        .manage(Webhooks::default())
+        .manage(Notifications::default())
+        .manage(Hotkeys::default())
         .invoke_handler(tauri::generate_handler![
             connect_to_league,
             update_champ_select_config,
             get_champ_select_config,
+            update_ready_check_policy,
+            update_post_game_config,
+            update_invite_config,
+            update_dodge_protection_config,
+            set_log_level,
+            export_logs,
+            cancel_pending_accept,
             get_all_champions,
+            refresh_champion_dataset,
+            get_activity_history,
+            get_activity_stats,
+            validate_priority_lists,
             start_auto_accept,
             stop_auto_accept,
             is_auto_accept_running,
             manual_accept,
+            update_control_api_config,
+            update_webhooks,
+            send_test_webhook,
+            update_notification_config,
+            update_hotkeys,
+            set_start_with_client,
+            set_autostart,
+            is_autostart_enabled,
+            update_lobby_config,
+            create_lobby,
+            set_position_preferences,
+            invite_friends,
+            start_matchmaking,
+            quick_queue,
             show_window,
             hide_window
         ])
         .setup(|app| {
-            let show_item = MenuItem::with_id(app, "show", "Show", true, None::<&str>)?;
-            let quit_item = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-            let menu = Menu::with_items(app, &[&show_item, &quit_item])?;
+            if let Ok(cache_dir) = app.path().app_cache_dir() {
+                champion_catalog::set_cache_dir(cache_dir);
+            }
+            
+            // Logging starts before the config is read so its warnings are kept.
+            match app.path().app_log_dir() {
+                Ok(log_dir) => {
+                    if let Err(e) = logging::init(&log_dir, logging::DEFAULT_LEVEL) {
+                        eprintln!("Could not set up logging: {}", e);
+                    }
+                }
+                Err(e) => eprintln!("Could not resolve log directory: {}", e),
+            }
+            info!(version = env!("CARGO_PKG_VERSION"), "Application started");
+            
+            let saved_config = AppPaths::from_app(app.handle())
+                .map(|paths| config_store::load(&paths.config_file()))
+                .unwrap_or_default();
+            if let Err(e) = logging::set_level(&saved_config.app.log_level) {
+                warn!("Keeping the default log level: {}", e);
+            }
+            
+            if saved_config.control_api.enabled {
+                let app_handle = app.handle().clone();
+                let control_api = saved_config.control_api.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = restart_control_api(&app_handle, &control_api).await {
+                        warn!("Could not start the control API: {}", e);
+                    }
+                });
+            }
+            app.state::<Webhooks>().set(saved_config.webhooks.clone());
+            app.state::<Notifications>().set(saved_config.notifications.clone());
+            if let Err(e) = app.state::<Hotkeys>().apply(app.handle(), &saved_config.hotkeys) {
+                warn!("Could not register global shortcuts: {}", e);
+            }
+            
+            let tray_status = TrayStatus {
+                auto_pick: saved_config.auto_pick_enabled,
+                auto_ban: saved_config.auto_ban_enabled,
+                ..TrayStatus::default()
+            };
+            *app.state::<ConfigState>().lock().unwrap() = saved_config;
+            
+            let icon = app.default_window_icon().unwrap().clone().to_owned();
+            let (tray_menu, menu) = TrayMenu::new(app.handle(), icon.clone(), tray_status.clone())?;
+            app.manage(tray_menu);
             
-            let _tray = TrayIconBuilder::new()
+            let _tray = TrayIconBuilder::with_id(TRAY_ID)
                 .menu(&menu)
-                .tooltip("Lolytics Auto Accept")
-                .icon(app.default_window_icon().unwrap().clone())
+                .tooltip(tray_status.tooltip())
+                .icon(icon)
                 .on_menu_event(|app, event| {
                     match event.id.as_ref() {
                         "show" => {
@@ -273,6 +1007,51 @@ pub fn run() {
                                 let _ = show_window(app_handle).await;
                             });
                         }
+                        "quick_queue" => {
+                            let app_handle = app.clone();
+                            let lobby_config = app.state::<ConfigState>().lock().unwrap().lobby.clone();
+                            tauri::async_runtime::spawn(async move {
+                                let events: &dyn EventSink = &app_handle;
+                                match run_quick_queue(lobby_config).await {
+                                    Ok(message) => events.emit("queue-started", message),
+                                    Err(e) => events.emit("queue-failed", e),
+                                }
+                            });
+                        }
+                        "auto_accept" => {
+                            let app_handle = app.clone();
+                            tauri::async_runtime::spawn(async move {
+                                if let Err(e) = toggle_service(app_handle.clone()).await {
+                                    warn!("Could not toggle auto-accept from the tray: {}", e);
+                                    // Undo the click's check mark.
+                                    update_tray(&app_handle, |_| {});
+                                }
+                            });
+                        }
+                        "auto_pick" | "auto_ban" => {
+                            let config = {
+                                let config_state = app.state::<ConfigState>();
+                                let mut config = config_state.lock().unwrap();
+                                if event.id.as_ref() == "auto_pick" {
+                                    config.auto_pick_enabled = !config.auto_pick_enabled;
+                                } else {
+                                    config.auto_ban_enabled = !config.auto_ban_enabled;
+                                }
+                                config.clone()
+                            };
+                            if let Err(e) = save_config(app, &config) {
+                                warn!("{}", e);
+                            }
+                            sync_toggles(app, &config);
+                        }
+                        "manual_accept" => {
+                            tauri::async_runtime::spawn(async move {
+                                match manual_accept().await {
+                                    Ok(message) => info!("{}", message),
+                                    Err(e) => warn!("{}", e),
+                                }
+                            });
+                        }
                         "quit" => {
                             app.exit(0);
                         }
@@ -297,6 +1076,14 @@ pub fn run() {
                     }
                 })
                 .build(app)?;
+            update_tray(app.handle(), |_| {});
+            
+            if std::env::args().any(|arg| arg == MINIMIZED_ARG) {
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.hide();
+                }
+            }
+            spawn_client_watcher(app.handle().clone());
 
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
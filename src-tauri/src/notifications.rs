@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "desktop")]
+use {
+    crate::events,
+    serde_json::Value,
+    std::sync::RwLock,
+    tauri::Manager,
+    tauri_plugin_notification::NotificationExt,
+    tracing::warn,
+};
+
+/// Desktop notifications for service events, so the app can stay in the tray.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NotificationConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Skip notifications while the window is open, where the event is already shown.
+    #[serde(default = "default_only_when_hidden")]
+    pub only_when_hidden: bool,
+    #[serde(default = "default_events")]
+    pub events: Vec<EventNotification>,
+}
+
+/// Notify about `event`, optionally with a sound.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventNotification {
+    pub event: String,
+    /// A system sound name: e.g. `Default` or `Reminder` on Windows, `Glass`
+    /// or `Ping` on macOS, `message-new-instant` on Linux. No sound when unset.
+    #[serde(default)]
+    pub sound: Option<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_only_when_hidden() -> bool {
+    true
+}
+
+fn default_events() -> Vec<EventNotification> {
+    ["match-accepted", "champion-pick-failed", "champion-ban-failed", "league-disconnected", "league-client-closed"]
+        .map(|event| EventNotification { event: event.to_string(), sound: None })
+        .to_vec()
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            only_when_hidden: default_only_when_hidden(),
+            events: default_events(),
+        }
+    }
+}
+
+impl NotificationConfig {
+    /// How to notify about `event`, if at all.
+    pub fn notification_for(&self, event: &str, window_visible: bool) -> Option<&EventNotification> {
+        if !self.enabled || (self.only_when_hidden && window_visible) {
+            return None;
+        }
+        self.events.iter().find(|notification| notification.event == event)
+    }
+}
+
+/// Shows desktop notifications for service events, following the current config.
+#[cfg(feature = "desktop")]
+#[derive(Default)]
+pub struct Notifications {
+    config: RwLock<NotificationConfig>,
+}
+
+#[cfg(feature = "desktop")]
+impl Notifications {
+    pub fn set(&self, config: NotificationConfig) {
+        *self.config.write().unwrap() = config;
+    }
+
+    pub fn notify(&self, app_handle: &tauri::AppHandle, event: &str, payload: &Value) {
+        let window_visible = app_handle
+            .get_webview_window("main")
+            .and_then(|window| window.is_visible().ok())
+            .unwrap_or(false);
+
+        let config = self.config.read().unwrap();
+        let Some(notification) = config.notification_for(event, window_visible) else {
+            return;
+        };
+
+        let mut builder = app_handle
+            .notification()
+            .builder()
+            .title(events::title(event))
+            .body(events::describe(payload));
+        if let Some(sound) = &notification.sound {
+            builder = builder.sound(sound);
+        }
+        if let Err(e) = builder.show() {
+            warn!(event, "Could not show notification: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notifies_about_selected_events_while_hidden() {
+        let mut config = NotificationConfig::default();
+        config.events[0].sound = Some("Ping".to_string());
+
+        let accepted = config.notification_for("match-accepted", false).unwrap();
+        assert_eq!(accepted.sound.as_deref(), Some("Ping"));
+        assert!(config.notification_for("champion-picked", false).is_none());
+        assert!(config.notification_for("match-accepted", true).is_none());
+        assert!(config.notification_for("league-client-closed", false).is_some());
+
+        config.only_when_hidden = false;
+        assert!(config.notification_for("match-accepted", true).is_some());
+
+        config.enabled = false;
+        assert!(config.notification_for("match-accepted", false).is_none());
+    }
+}
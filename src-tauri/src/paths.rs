@@ -0,0 +1,73 @@
+use crate::config_store::CONFIG_FILE;
+use crate::counter_picks::COUNTER_PICKS_FILE;
+use crate::history::HISTORY_FILE;
+use crate::league_client::LeagueError;
+use std::path::PathBuf;
+#[cfg(feature = "desktop")]
+use tauri::Manager;
+
+/// `identifier` from `tauri.conf.json`; the app's directories are named after it.
+pub const APP_IDENTIFIER: &str = "com.lolytics.league-auto-accept";
+
+/// Where the app keeps its files. The desktop app asks Tauri; the CLI
+/// resolves the same platform directories itself, so both share one config
+/// and one history.
+#[derive(Debug, Clone)]
+pub struct AppPaths {
+    pub config_dir: PathBuf,
+    pub data_dir: PathBuf,
+    pub cache_dir: PathBuf,
+    pub log_dir: PathBuf,
+}
+
+impl AppPaths {
+    #[cfg(feature = "desktop")]
+    pub fn from_app(app_handle: &tauri::AppHandle) -> Result<Self, LeagueError> {
+        let path = app_handle.path();
+        let resolve = |dir: tauri::Result<PathBuf>, kind: &str| {
+            dir.map_err(|e| LeagueError::from(format!("Could not resolve {} directory: {}", kind, e)))
+        };
+
+        Ok(Self {
+            config_dir: resolve(path.app_config_dir(), "config")?,
+            data_dir: resolve(path.app_data_dir(), "data")?,
+            cache_dir: resolve(path.app_cache_dir(), "cache")?,
+            log_dir: resolve(path.app_log_dir(), "log")?,
+        })
+    }
+
+    /// The directories Tauri would resolve for `APP_IDENTIFIER`, without a running app.
+    pub fn standalone() -> Result<Self, LeagueError> {
+        let resolve = |dir: Option<PathBuf>, kind: &str| {
+            dir.map(|dir| dir.join(APP_IDENTIFIER))
+                .ok_or_else(|| LeagueError::from(format!("Could not resolve {} directory", kind)))
+        };
+
+        // Tauri puts logs in ~/Library/Logs on macOS and under the local data
+        // directory everywhere else.
+        let log_dir = if cfg!(target_os = "macos") {
+            resolve(dirs::home_dir().map(|home| home.join("Library").join("Logs")), "log")?
+        } else {
+            resolve(dirs::data_local_dir(), "log")?.join("logs")
+        };
+
+        Ok(Self {
+            config_dir: resolve(dirs::config_dir(), "config")?,
+            data_dir: resolve(dirs::data_dir(), "data")?,
+            cache_dir: resolve(dirs::cache_dir(), "cache")?,
+            log_dir,
+        })
+    }
+
+    pub fn config_file(&self) -> PathBuf {
+        self.config_dir.join(CONFIG_FILE)
+    }
+
+    pub fn counter_picks_file(&self) -> PathBuf {
+        self.config_dir.join(COUNTER_PICKS_FILE)
+    }
+
+    pub fn history_file(&self) -> PathBuf {
+        self.data_dir.join(HISTORY_FILE)
+    }
+}
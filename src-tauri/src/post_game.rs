@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+
+/// Opt-in handling of the screens after a game ends.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PostGameConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_true")]
+    pub skip_honor: bool,
+    #[serde(default = "default_true")]
+    pub dismiss_stats: bool,
+    /// Start matchmaking again after returning to the lobby (party leader only).
+    #[serde(default = "default_true")]
+    pub requeue: bool,
+    /// Stop re-queueing after this many games in one monitoring session; 0 means no limit.
+    #[serde(default)]
+    pub max_games: u32,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for PostGameConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            skip_honor: true,
+            dismiss_stats: true,
+            requeue: true,
+            max_games: 0,
+        }
+    }
+}
+
+impl PostGameConfig {
+    pub fn session_cap_reached(&self, games_played: u32) -> bool {
+        self.max_games > 0 && games_played >= self.max_games
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PostGameProgress {
+    pub message: String,
+    pub games_played: u32,
+    pub max_games: u32,
+}
+
+/// The next thing to do after a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostGameStep {
+    SkipHonor(i64),
+    /// Dismiss the stats screen and leave the end-of-game screen.
+    ReturnToLobby(i64),
+    /// Back in the lobby after a game; start matchmaking.
+    Requeue,
+}
+
+/// How far the post-game flow got. A step is only marked done once the client
+/// gave a definite answer, so a failed request is tried again on the next poll.
+#[derive(Debug, Default)]
+pub struct PostGameTracker {
+    honor_skipped_game: Option<i64>,
+    counted_game: Option<i64>,
+    handled_game: Option<i64>,
+    requeue_pending: bool,
+    pub games_played: u32,
+}
+
+impl PostGameTracker {
+    pub fn next_step(&mut self, config: &PostGameConfig, phase: &str, game_id: Option<i64>) -> Option<PostGameStep> {
+        // Queued by hand or in another game: nothing left to re-queue. The
+        // end-of-game screen can linger briefly after play-again.
+        if phase != "Lobby" && phase != "EndOfGame" {
+            self.requeue_pending = false;
+        }
+
+        match (phase, game_id) {
+            ("PreEndOfGame", Some(game_id)) if config.skip_honor && self.honor_skipped_game != Some(game_id) => {
+                Some(PostGameStep::SkipHonor(game_id))
+            }
+            ("EndOfGame", Some(game_id)) if self.handled_game != Some(game_id) => Some(PostGameStep::ReturnToLobby(game_id)),
+            ("Lobby", _) if self.requeue_pending => Some(PostGameStep::Requeue),
+            _ => None,
+        }
+    }
+
+    pub fn honor_skipped(&mut self, game_id: i64) {
+        self.honor_skipped_game = Some(game_id);
+    }
+
+    /// Counts `game_id` toward the session, once. Returns whether it was new.
+    pub fn count_game(&mut self, game_id: i64) -> bool {
+        if self.counted_game == Some(game_id) {
+            return false;
+        }
+        self.counted_game = Some(game_id);
+        self.games_played += 1;
+        true
+    }
+
+    /// The end-of-game screen is done with. `requeue` leaves matchmaking to
+    /// be started from the lobby.
+    pub fn returned_to_lobby(&mut self, game_id: i64, requeue: bool) {
+        self.handled_game = Some(game_id);
+        self.requeue_pending = requeue;
+    }
+
+    pub fn requeue_finished(&mut self) {
+        self.requeue_pending = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_cap() {
+        let config = PostGameConfig { max_games: 3, ..PostGameConfig::default() };
+        assert!(!config.session_cap_reached(2));
+        assert!(config.session_cap_reached(3));
+        assert!(config.session_cap_reached(4));
+
+        let unlimited = PostGameConfig::default();
+        assert!(!unlimited.session_cap_reached(100));
+    }
+
+    #[test]
+    fn steps_follow_the_gameflow_phase() {
+        let config = PostGameConfig::default();
+        let mut tracker = PostGameTracker::default();
+
+        assert_eq!(tracker.next_step(&config, "InProgress", Some(7)), None);
+        assert_eq!(tracker.next_step(&config, "PreEndOfGame", Some(7)), Some(PostGameStep::SkipHonor(7)));
+        tracker.honor_skipped(7);
+        assert_eq!(tracker.next_step(&config, "PreEndOfGame", Some(7)), None);
+
+        assert_eq!(tracker.next_step(&config, "EndOfGame", Some(7)), Some(PostGameStep::ReturnToLobby(7)));
+        tracker.returned_to_lobby(7, true);
+        assert_eq!(tracker.next_step(&config, "EndOfGame", Some(7)), None);
+
+        assert_eq!(tracker.next_step(&config, "Lobby", None), Some(PostGameStep::Requeue));
+        tracker.requeue_finished();
+        assert_eq!(tracker.next_step(&config, "Lobby", None), None);
+    }
+
+    #[test]
+    fn needs_a_game_id_and_honors_skip_honor() {
+        let mut tracker = PostGameTracker::default();
+        assert_eq!(tracker.next_step(&PostGameConfig::default(), "EndOfGame", None), None);
+
+        let keep_honor = PostGameConfig { skip_honor: false, ..PostGameConfig::default() };
+        assert_eq!(tracker.next_step(&keep_honor, "PreEndOfGame", Some(7)), None);
+    }
+
+    #[test]
+    fn manual_queueing_cancels_a_pending_requeue() {
+        let config = PostGameConfig::default();
+        let mut tracker = PostGameTracker::default();
+        tracker.returned_to_lobby(7, true);
+
+        assert_eq!(tracker.next_step(&config, "Matchmaking", None), None);
+        assert_eq!(tracker.next_step(&config, "Lobby", None), None);
+    }
+
+    #[test]
+    fn games_are_counted_once() {
+        let mut tracker = PostGameTracker::default();
+        assert!(tracker.count_game(7));
+        assert!(!tracker.count_game(7));
+        assert!(tracker.count_game(8));
+        assert_eq!(tracker.games_played, 2);
+    }
+}
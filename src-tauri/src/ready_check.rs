@@ -0,0 +1,204 @@
+use chrono::{NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadyCheckMode {
+    #[default]
+    Accept,
+    /// "Taking a break": every ready check is declined.
+    Decline,
+}
+
+/// Local time-of-day window in `HH:MM` form. `start > end` wraps past midnight.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AcceptWindow {
+    pub start: String,
+    pub end: String,
+}
+
+impl AcceptWindow {
+    fn parse(value: &str) -> Result<NaiveTime, String> {
+        NaiveTime::parse_from_str(value, "%H:%M")
+            .map_err(|_| format!("Invalid time '{}', expected HH:MM", value))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        Self::parse(&self.start)?;
+        Self::parse(&self.end)?;
+        Ok(())
+    }
+
+    pub fn contains(&self, now: NaiveTime) -> bool {
+        let (Ok(start), Ok(end)) = (Self::parse(&self.start), Self::parse(&self.end)) else {
+            return true;
+        };
+        let now = NaiveTime::from_hms_opt(now.hour(), now.minute(), 0).unwrap_or(now);
+
+        if start <= end {
+            now >= start && now < end
+        } else {
+            now >= start || now < end
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ReadyCheckPolicy {
+    #[serde(default)]
+    pub mode: ReadyCheckMode,
+    #[serde(default)]
+    pub accept_delay_secs: u64,
+    #[serde(default)]
+    pub time_window: Option<AcceptWindow>,
+    /// Queue IDs to accept for; empty means every queue.
+    #[serde(default)]
+    pub allowed_queues: Vec<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReadyCheckDecision {
+    Accept { delay_secs: u64 },
+    Decline,
+    /// Leave the ready check to the player.
+    Ignore(String),
+}
+
+impl ReadyCheckPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(window) = &self.time_window {
+            window.validate()?;
+        }
+        Ok(())
+    }
+
+    pub fn decide(&self, now: NaiveTime, queue_id: Option<i64>) -> ReadyCheckDecision {
+        if self.mode == ReadyCheckMode::Decline {
+            return ReadyCheckDecision::Decline;
+        }
+
+        if let Some(window) = &self.time_window {
+            if !window.contains(now) {
+                return ReadyCheckDecision::Ignore(format!(
+                    "Outside accept window ({}-{})",
+                    window.start, window.end
+                ));
+            }
+        }
+
+        if !self.allowed_queues.is_empty() {
+            match queue_id {
+                Some(id) if self.allowed_queues.contains(&id) => {}
+                Some(id) => return ReadyCheckDecision::Ignore(format!("Queue {} is not in the accept list", id)),
+                None => return ReadyCheckDecision::Ignore("Could not determine the queue".to_string()),
+            }
+        }
+
+        ReadyCheckDecision::Accept { delay_secs: self.accept_delay_secs }
+    }
+}
+
+/// Shared between the monitoring loop and the `cancel_pending_accept` command.
+#[derive(Debug, Default)]
+pub struct ReadyCheckControl {
+    countdown_active: AtomicBool,
+    cancel_requested: AtomicBool,
+}
+
+impl ReadyCheckControl {
+    pub fn begin_countdown(&self) {
+        self.cancel_requested.store(false, Ordering::SeqCst);
+        self.countdown_active.store(true, Ordering::SeqCst);
+    }
+
+    pub fn end_countdown(&self) {
+        self.countdown_active.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel_requested.load(Ordering::SeqCst)
+    }
+
+    /// Returns `false` when there is no countdown to cancel.
+    pub fn request_cancel(&self) -> bool {
+        if !self.countdown_active.load(Ordering::SeqCst) {
+            return false;
+        }
+        self.cancel_requested.store(true, Ordering::SeqCst);
+        true
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadyCheckCountdown {
+    pub seconds_left: u64,
+    pub queue_id: Option<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 30).unwrap()
+    }
+
+    fn window(start: &str, end: &str) -> AcceptWindow {
+        AcceptWindow { start: start.to_string(), end: end.to_string() }
+    }
+
+    #[test]
+    fn windows_include_the_start_and_exclude_the_end() {
+        let evening = window("18:00", "23:00");
+        assert!(evening.contains(at(18, 0)));
+        assert!(evening.contains(at(22, 59)));
+        assert!(!evening.contains(at(23, 0)));
+        assert!(!evening.contains(at(9, 0)));
+    }
+
+    #[test]
+    fn windows_can_wrap_past_midnight() {
+        let night = window("22:00", "02:00");
+        assert!(night.contains(at(23, 15)));
+        assert!(night.contains(at(1, 59)));
+        assert!(!night.contains(at(2, 0)));
+        assert!(!night.contains(at(12, 0)));
+    }
+
+    #[test]
+    fn invalid_windows_are_rejected() {
+        assert!(window("18:00", "23:00").validate().is_ok());
+        assert_eq!(window("6pm", "23:00").validate(), Err("Invalid time '6pm', expected HH:MM".to_string()));
+    }
+
+    #[test]
+    fn accepts_by_default_with_the_configured_delay() {
+        let policy = ReadyCheckPolicy { accept_delay_secs: 3, ..ReadyCheckPolicy::default() };
+        assert_eq!(policy.decide(at(12, 0), None), ReadyCheckDecision::Accept { delay_secs: 3 });
+    }
+
+    #[test]
+    fn break_mode_declines_everything() {
+        let policy = ReadyCheckPolicy {
+            mode: ReadyCheckMode::Decline,
+            time_window: Some(window("18:00", "23:00")),
+            ..ReadyCheckPolicy::default()
+        };
+        assert_eq!(policy.decide(at(12, 0), Some(420)), ReadyCheckDecision::Decline);
+    }
+
+    #[test]
+    fn ignores_outside_the_window_and_other_queues() {
+        let policy = ReadyCheckPolicy {
+            time_window: Some(window("18:00", "23:00")),
+            allowed_queues: vec![420, 440],
+            ..ReadyCheckPolicy::default()
+        };
+
+        assert_eq!(policy.decide(at(12, 0), Some(420)), ReadyCheckDecision::Ignore("Outside accept window (18:00-23:00)".to_string()));
+        assert_eq!(policy.decide(at(20, 0), Some(450)), ReadyCheckDecision::Ignore("Queue 450 is not in the accept list".to_string()));
+        assert_eq!(policy.decide(at(20, 0), None), ReadyCheckDecision::Ignore("Could not determine the queue".to_string()));
+        assert_eq!(policy.decide(at(20, 0), Some(440)), ReadyCheckDecision::Accept { delay_secs: 0 });
+    }
+}
@@ -0,0 +1,86 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const FIRST_DELAY: Duration = Duration::from_secs(1);
+const MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// What the monitoring loop does when it loses the League client.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReconnectConfig {
+    /// How long to wait for the client to come back before stopping. A
+    /// restart or patch usually takes a few minutes.
+    #[serde(default = "default_give_up_after_secs")]
+    pub give_up_after_secs: u64,
+    /// How long the client's lockfile must be gone before the client counts
+    /// as closed and monitoring stops, without waiting out
+    /// `give_up_after_secs`. Long enough for the client to restart itself.
+    #[serde(default = "default_closed_after_secs")]
+    pub closed_after_secs: u64,
+}
+
+fn default_give_up_after_secs() -> u64 {
+    15 * 60
+}
+
+fn default_closed_after_secs() -> u64 {
+    30
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        Self {
+            give_up_after_secs: default_give_up_after_secs(),
+            closed_after_secs: default_closed_after_secs(),
+        }
+    }
+}
+
+/// Exponential backoff with jitter: each delay is a random point in the upper
+/// half of a step that doubles up to `MAX_DELAY`.
+#[derive(Debug)]
+pub struct Backoff {
+    step: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self { step: FIRST_DELAY }
+    }
+}
+
+impl Backoff {
+    pub fn next_delay(&mut self, rng: &mut impl Rng) -> Duration {
+        let step = self.step;
+        self.step = (step * 2).min(MAX_DELAY);
+
+        let half = step / 2;
+        half + half.mul_f64(rng.random::<f64>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn delays_double_up_to_the_cap_with_jitter() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut backoff = Backoff::default();
+
+        let steps = [1, 2, 4, 8, 16, 30, 30, 30].map(Duration::from_secs);
+        for step in steps {
+            let delay = backoff.next_delay(&mut rng);
+            assert!(delay >= step / 2 && delay <= step, "{:?} outside {:?}", delay, step);
+        }
+    }
+
+    #[test]
+    fn jitter_spreads_delays() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let delays: Vec<Duration> = (0..5).map(|_| Backoff::default().next_delay(&mut rng)).collect();
+        assert!(delays.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+}
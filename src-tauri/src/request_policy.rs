@@ -0,0 +1,117 @@
+use reqwest::{Method, StatusCode};
+use std::time::Duration;
+
+/// Why a League Client request didn't succeed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Failure {
+    /// The connection couldn't be made, so the request never reached the client.
+    Connect,
+    /// No answer within the timeout. The client may still have acted on it.
+    Timeout,
+    /// The connection broke some other way, possibly after the request was sent.
+    Transport,
+    Status(StatusCode),
+}
+
+impl Failure {
+    pub fn from_error(error: &reqwest::Error) -> Self {
+        if error.is_connect() {
+            Failure::Connect
+        } else if error.is_timeout() {
+            Failure::Timeout
+        } else {
+            Failure::Transport
+        }
+    }
+
+    /// `None` for a successful response.
+    pub fn from_status(status: StatusCode) -> Option<Self> {
+        (!status.is_success()).then_some(Failure::Status(status))
+    }
+}
+
+/// Timeout and retries for one kind of League Client request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub timeout: Duration,
+    /// Including the first try.
+    pub max_attempts: u32,
+    /// Waited before the second attempt, growing linearly after that.
+    pub delay: Duration,
+    /// Whether sending the request twice is harmless, so it can be retried
+    /// even when it may already have reached the client.
+    pub idempotent: bool,
+}
+
+impl RetryPolicy {
+    /// GETs: retried on any transport failure or server error.
+    pub const READ: RetryPolicy = RetryPolicy {
+        timeout: Duration::from_secs(5),
+        max_attempts: 3,
+        delay: Duration::from_millis(250),
+        idempotent: true,
+    };
+
+    /// POSTs, PUTs and PATCHes: retried only when the request never reached
+    /// the client or the client failed with a server error. A 4xx is the
+    /// client's answer, e.g. a champion that can't be picked.
+    pub const WRITE: RetryPolicy = RetryPolicy {
+        timeout: Duration::from_secs(10),
+        max_attempts: 3,
+        delay: Duration::from_millis(250),
+        idempotent: false,
+    };
+
+    pub fn for_method(method: &Method) -> Self {
+        if *method == Method::GET || *method == Method::HEAD {
+            Self::READ
+        } else {
+            Self::WRITE
+        }
+    }
+
+    pub fn retries(&self, failure: Failure) -> bool {
+        match failure {
+            Failure::Connect => true,
+            Failure::Status(status) => status.is_server_error(),
+            Failure::Timeout | Failure::Transport => self.idempotent,
+        }
+    }
+
+    /// How long to wait after the `attempt`th try failed.
+    pub fn delay_after(&self, attempt: u32) -> Duration {
+        self.delay * attempt
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_retry_transient_failures() {
+        let read = RetryPolicy::for_method(&Method::GET);
+        assert!(read.retries(Failure::Connect));
+        assert!(read.retries(Failure::Timeout));
+        assert!(read.retries(Failure::Transport));
+        assert!(read.retries(Failure::Status(StatusCode::INTERNAL_SERVER_ERROR)));
+        assert!(!read.retries(Failure::Status(StatusCode::NOT_FOUND)));
+    }
+
+    #[test]
+    fn writes_retry_only_unsent_requests_and_server_errors() {
+        let write = RetryPolicy::for_method(&Method::PATCH);
+        assert!(write.retries(Failure::Connect));
+        assert!(write.retries(Failure::Status(StatusCode::SERVICE_UNAVAILABLE)));
+        assert!(!write.retries(Failure::Timeout));
+        assert!(!write.retries(Failure::Transport));
+        assert!(!write.retries(Failure::Status(StatusCode::BAD_REQUEST)));
+        assert!(!write.retries(Failure::Status(StatusCode::CONFLICT)));
+    }
+
+    #[test]
+    fn successes_are_not_failures() {
+        assert_eq!(Failure::from_status(StatusCode::NO_CONTENT), None);
+        assert_eq!(Failure::from_status(StatusCode::BAD_GATEWAY), Some(Failure::Status(StatusCode::BAD_GATEWAY)));
+    }
+}
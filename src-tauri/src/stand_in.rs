@@ -0,0 +1,84 @@
+//! A local HTTP server for tests, standing in for the League Client or a
+//! webhook receiver.
+
+use axum::extract::State;
+use axum::http::{HeaderMap, Method, StatusCode, Uri};
+use axum::routing::any;
+use axum::Router;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+/// Answers with `statuses` in order, then 204, and records what it received.
+#[derive(Clone, Default)]
+pub struct StandIn {
+    statuses: Arc<Mutex<Vec<u16>>>,
+    error_body: &'static str,
+    delay: Duration,
+    requested: Arc<Mutex<Vec<String>>>,
+    received: Arc<Mutex<Vec<(HeaderMap, String)>>>,
+}
+
+impl StandIn {
+    pub fn new(statuses: &[u16]) -> Self {
+        Self { statuses: Arc::new(Mutex::new(statuses.to_vec())), ..Self::default() }
+    }
+
+    /// Sent along with each of the given statuses.
+    pub fn with_error_body(mut self, body: &'static str) -> Self {
+        self.error_body = body;
+        self
+    }
+
+    /// Waits this long before answering, e.g. to outlast a client's timeout.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Serves `path` (axum syntax, e.g. `/actions/:id` or `/*path` for
+    /// everything) for any method on a free local port, and returns the
+    /// server's base URL.
+    pub async fn serve(&self, path: &str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let router = Router::new().route(path, any(answer)).with_state(self.clone());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        base_url
+    }
+
+    /// `METHOD /path` of each request, in order.
+    pub fn requested(&self) -> Vec<String> {
+        self.requested.lock().unwrap().clone()
+    }
+
+    /// The headers and body of each request, in order.
+    pub fn received(&self) -> Vec<(HeaderMap, String)> {
+        self.received.lock().unwrap().clone()
+    }
+
+    pub fn requests(&self) -> usize {
+        self.received.lock().unwrap().len()
+    }
+}
+
+async fn answer(
+    State(stand_in): State<StandIn>,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    body: String,
+) -> (StatusCode, &'static str) {
+    stand_in.requested.lock().unwrap().push(format!("{} {}", method, uri.path()));
+    stand_in.received.lock().unwrap().push((headers, body));
+    tokio::time::sleep(stand_in.delay).await;
+
+    let status = {
+        let mut statuses = stand_in.statuses.lock().unwrap();
+        (!statuses.is_empty()).then(|| statuses.remove(0))
+    };
+    match status {
+        Some(status) => (StatusCode::from_u16(status).unwrap(), stand_in.error_body),
+        None => (StatusCode::NO_CONTENT, ""),
+    }
+}
@@ -0,0 +1,157 @@
+use std::sync::Mutex;
+use tauri::image::Image;
+use tauri::menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem};
+use tauri::Wry;
+use tracing::warn;
+
+pub const TRAY_ID: &str = "auto-accept";
+
+/// What the tray shows: connection, monitoring and the pick/ban toggles.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrayStatus {
+    pub connected_as: Option<String>,
+    /// Auto-accept is on but the client went away; it resumes when the client is back.
+    pub waiting_for_client: bool,
+    pub auto_accept: bool,
+    pub auto_pick: bool,
+    pub auto_ban: bool,
+}
+
+impl TrayStatus {
+    pub fn connection(&self) -> String {
+        if self.waiting_for_client {
+            return "Waiting for League client".to_string();
+        }
+        match &self.connected_as {
+            Some(name) => format!("Connected as {}", name),
+            None => "Not connected".to_string(),
+        }
+    }
+
+    pub fn tooltip(&self) -> String {
+        let auto_accept = if self.auto_accept { "ON" } else { "OFF" };
+        format!("{} — auto-accept {}", self.connection(), auto_accept)
+    }
+}
+
+/// A grey, half-transparent copy of an RGBA icon, shown while auto-accept is off.
+fn dimmed(rgba: &[u8]) -> Vec<u8> {
+    rgba.chunks_exact(4)
+        .flat_map(|pixel| {
+            let [r, g, b, a] = [pixel[0], pixel[1], pixel[2], pixel[3]].map(u32::from);
+            let grey = ((r * 299 + g * 587 + b * 114) / 1000) as u8;
+            [grey, grey, grey, (a / 2) as u8]
+        })
+        .collect()
+}
+
+/// The tray menu's stateful items. Menu events use the item IDs; the status
+/// line is informational only.
+pub struct TrayMenu {
+    status: MenuItem<Wry>,
+    auto_accept: CheckMenuItem<Wry>,
+    auto_pick: CheckMenuItem<Wry>,
+    auto_ban: CheckMenuItem<Wry>,
+    active_icon: Image<'static>,
+    idle_icon: Image<'static>,
+    current: Mutex<TrayStatus>,
+}
+
+impl TrayMenu {
+    pub fn new(app_handle: &tauri::AppHandle, icon: Image<'static>, status: TrayStatus) -> tauri::Result<(Self, Menu<Wry>)> {
+        let status_item = MenuItem::with_id(app_handle, "status", &status.connection(), false, None::<&str>)?;
+        let auto_accept = CheckMenuItem::with_id(app_handle, "auto_accept", "Auto-Accept", true, status.auto_accept, None::<&str>)?;
+        let auto_pick = CheckMenuItem::with_id(app_handle, "auto_pick", "Auto-Pick", true, status.auto_pick, None::<&str>)?;
+        let auto_ban = CheckMenuItem::with_id(app_handle, "auto_ban", "Auto-Ban", true, status.auto_ban, None::<&str>)?;
+        let accept_item = MenuItem::with_id(app_handle, "manual_accept", "Accept Match", true, None::<&str>)?;
+        let show_item = MenuItem::with_id(app_handle, "show", "Show", true, None::<&str>)?;
+        let queue_item = MenuItem::with_id(app_handle, "quick_queue", "Find Match", true, None::<&str>)?;
+        let quit_item = MenuItem::with_id(app_handle, "quit", "Quit", true, None::<&str>)?;
+
+        let menu = Menu::with_items(app_handle, &[
+            &status_item,
+            &PredefinedMenuItem::separator(app_handle)?,
+            &auto_accept,
+            &auto_pick,
+            &auto_ban,
+            &accept_item,
+            &PredefinedMenuItem::separator(app_handle)?,
+            &show_item,
+            &queue_item,
+            &quit_item,
+        ])?;
+
+        let idle_icon = Image::new_owned(dimmed(icon.rgba()), icon.width(), icon.height());
+        let tray_menu = Self {
+            status: status_item,
+            auto_accept,
+            auto_pick,
+            auto_ban,
+            active_icon: icon,
+            idle_icon,
+            current: Mutex::new(status),
+        };
+        Ok((tray_menu, menu))
+    }
+
+    /// Follows the service's connection events.
+    pub fn on_event(&self, app_handle: &tauri::AppHandle, event: &str) {
+        match event {
+            "waiting-for-client" => self.update(app_handle, |status| status.waiting_for_client = true),
+            "league-reconnected" => self.update(app_handle, |status| status.waiting_for_client = false),
+            _ => {}
+        }
+    }
+
+    /// Applies `change` and redraws the tray. Check items are always reset,
+    /// since clicking one toggles it before we know whether the change worked.
+    pub fn update(&self, app_handle: &tauri::AppHandle, change: impl FnOnce(&mut TrayStatus)) {
+        let status = {
+            let mut current = self.current.lock().unwrap();
+            change(&mut current);
+            current.clone()
+        };
+
+        if let Err(e) = self.redraw(app_handle, &status) {
+            warn!("Could not update the tray: {}", e);
+        }
+    }
+
+    fn redraw(&self, app_handle: &tauri::AppHandle, status: &TrayStatus) -> tauri::Result<()> {
+        self.status.set_text(status.connection())?;
+        self.auto_accept.set_checked(status.auto_accept)?;
+        self.auto_pick.set_checked(status.auto_pick)?;
+        self.auto_ban.set_checked(status.auto_ban)?;
+
+        if let Some(tray) = app_handle.tray_by_id(TRAY_ID) {
+            let icon = if status.auto_accept { &self.active_icon } else { &self.idle_icon };
+            tray.set_tooltip(Some(status.tooltip()))?;
+            tray.set_icon(Some(icon.clone()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tooltip_shows_connection_and_auto_accept() {
+        let mut status = TrayStatus::default();
+        assert_eq!(status.tooltip(), "Not connected — auto-accept OFF");
+
+        status.connected_as = Some("Faker#KR1".to_string());
+        status.auto_accept = true;
+        assert_eq!(status.tooltip(), "Connected as Faker#KR1 — auto-accept ON");
+
+        status.waiting_for_client = true;
+        assert_eq!(status.tooltip(), "Waiting for League client — auto-accept ON");
+    }
+
+    #[test]
+    fn idle_icon_is_grey_and_faded() {
+        let rgba = [255, 0, 0, 255, 10, 20, 30, 0];
+        assert_eq!(dimmed(&rgba), vec![76, 76, 76, 127, 18, 18, 18, 0]);
+    }
+}
@@ -0,0 +1,337 @@
+use crate::events;
+use crate::league_client::LeagueError;
+use reqwest::{Client, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::{Arc, RwLock};
+use tokio::time::{sleep, Duration};
+use tracing::{debug, warn};
+
+/// Sent by the "send test" command.
+pub const TEST_EVENT: &str = "webhook-test";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Doubled after every failed attempt.
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// How the event is laid out in the request.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookFormat {
+    /// `{"event", "title", "message", "timestamp", "payload"}`
+    #[default]
+    Json,
+    /// A Discord channel webhook URL.
+    Discord,
+    /// An ntfy topic URL, e.g. `https://ntfy.sh/my-topic`.
+    Ntfy,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub url: String,
+    #[serde(default)]
+    pub format: WebhookFormat,
+    /// The events that fire this webhook, e.g. `match-accepted` or `champion-pick-failed`.
+    #[serde(default = "default_events")]
+    pub events: Vec<String>,
+    /// Further attempts after a failed delivery. Requests the receiver rejects
+    /// outright (4xx) are not retried.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_events() -> Vec<String> {
+    ["match-accepted", "champion-pick-failed", "league-disconnected"]
+        .map(String::from)
+        .to_vec()
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+impl WebhookConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        match Url::parse(self.url.trim()) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(()),
+            _ => Err(format!("Invalid webhook URL '{}': expected an http(s) address", self.url)),
+        }
+    }
+
+    pub fn fires_on(&self, event: &str) -> bool {
+        self.enabled && self.events.iter().any(|subscribed| subscribed == event)
+    }
+}
+
+/// A rendered webhook request.
+#[derive(Debug, PartialEq)]
+struct Delivery {
+    body: String,
+    content_type: &'static str,
+    headers: Vec<(&'static str, String)>,
+}
+
+fn is_problem(event: &str) -> bool {
+    event.ends_with("-failed") || event == "league-disconnected"
+}
+
+fn render(format: WebhookFormat, event: &str, payload: &Value) -> Delivery {
+    let title = events::title(event);
+    let message = events::describe(payload);
+
+    match format {
+        WebhookFormat::Json => Delivery {
+            body: json!({
+                "event": event,
+                "title": title,
+                "message": message,
+                "timestamp": chrono::Local::now().to_rfc3339(),
+                "payload": payload,
+            })
+            .to_string(),
+            content_type: "application/json",
+            headers: Vec::new(),
+        },
+        WebhookFormat::Discord => Delivery {
+            body: json!({
+                "username": "League Auto-Accept",
+                "content": format!("**{}**\n{}", title, message),
+            })
+            .to_string(),
+            content_type: "application/json",
+            headers: Vec::new(),
+        },
+        WebhookFormat::Ntfy => Delivery {
+            body: message,
+            content_type: "text/plain; charset=utf-8",
+            headers: vec![
+                ("Title", title),
+                ("Tags", if is_problem(event) { "warning" } else { "video_game" }.to_string()),
+            ],
+        },
+    }
+}
+
+enum DeliveryError {
+    /// Worth another attempt: the receiver was unreachable, overloaded or rate limiting.
+    Transient(String),
+    Rejected(String),
+}
+
+/// Sends service events to the configured webhooks. Deliveries run in the
+/// background, so a slow or unreachable receiver never holds up the service.
+#[derive(Clone)]
+pub struct Webhooks {
+    client: Client,
+    hooks: Arc<RwLock<Vec<WebhookConfig>>>,
+    retry_delay: Duration,
+}
+
+impl Default for Webhooks {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl Webhooks {
+    pub fn new(hooks: Vec<WebhookConfig>) -> Self {
+        Self {
+            client: Client::builder().timeout(REQUEST_TIMEOUT).build().unwrap_or_default(),
+            hooks: Arc::new(RwLock::new(hooks)),
+            retry_delay: RETRY_DELAY,
+        }
+    }
+
+    pub fn set(&self, hooks: Vec<WebhookConfig>) {
+        *self.hooks.write().unwrap() = hooks;
+    }
+
+    /// Delivers `event` to every webhook subscribed to it.
+    pub fn notify(&self, event: &str, payload: &Value) {
+        let hooks: Vec<WebhookConfig> = self.hooks.read().unwrap()
+            .iter()
+            .filter(|hook| hook.fires_on(event))
+            .cloned()
+            .collect();
+
+        for hook in hooks {
+            let webhooks = self.clone();
+            let event = event.to_string();
+            let payload = payload.clone();
+            tokio::spawn(async move {
+                if let Err(e) = webhooks.deliver(&hook, &event, &payload).await {
+                    warn!(event, "Webhook delivery failed: {}", e);
+                }
+            });
+        }
+    }
+
+    /// Sends a test event to `hook` once, whatever events it is subscribed to.
+    pub async fn send_test(&self, hook: &WebhookConfig) -> Result<(), LeagueError> {
+        hook.validate()?;
+        let payload = json!({ "message": "Test notification from League Auto-Accept" });
+        let delivery = render(hook.format, TEST_EVENT, &payload);
+
+        self.post(hook, &delivery).await.map_err(|e| match e {
+            DeliveryError::Transient(message) | DeliveryError::Rejected(message) => message.into(),
+        })
+    }
+
+    async fn deliver(&self, hook: &WebhookConfig, event: &str, payload: &Value) -> Result<(), LeagueError> {
+        let delivery = render(hook.format, event, payload);
+        let mut delay = self.retry_delay;
+        let mut attempt = 0;
+
+        loop {
+            match self.post(hook, &delivery).await {
+                Ok(()) => return Ok(()),
+                Err(DeliveryError::Transient(message)) if attempt < hook.max_retries => {
+                    attempt += 1;
+                    debug!(event, attempt, "Webhook delivery failed, retrying: {}", message);
+                    sleep(delay).await;
+                    delay *= 2;
+                }
+                Err(DeliveryError::Transient(message)) => {
+                    return Err(format!("{} (gave up after {} attempts)", message, attempt + 1).into());
+                }
+                Err(DeliveryError::Rejected(message)) => return Err(message.into()),
+            }
+        }
+    }
+
+    async fn post(&self, hook: &WebhookConfig, delivery: &Delivery) -> Result<(), DeliveryError> {
+        let mut request = self.client
+            .post(hook.url.trim())
+            .header(reqwest::header::CONTENT_TYPE, delivery.content_type)
+            .body(delivery.body.clone());
+        for (name, value) in &delivery.headers {
+            request = request.header(*name, value);
+        }
+
+        let response = request.send().await
+            // The URL often carries the webhook's token, so keep it out of logs and errors.
+            .map_err(|e| DeliveryError::Transient(format!("Could not reach webhook: {}", e.without_url())))?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        let message = format!("Webhook returned {}: {}", status, body.trim());
+        if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            Err(DeliveryError::Transient(message))
+        } else {
+            Err(DeliveryError::Rejected(message))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stand_in::StandIn;
+    use tokio::net::TcpListener;
+
+    async fn stand_in(statuses: &[u16]) -> (StandIn, String) {
+        let stand_in = StandIn::new(statuses);
+        let url = format!("{}/hook", stand_in.serve("/hook").await);
+        (stand_in, url)
+    }
+
+    fn hook(url: &str, format: WebhookFormat) -> WebhookConfig {
+        WebhookConfig { enabled: true, url: url.to_string(), format, events: default_events(), max_retries: 2 }
+    }
+
+    fn webhooks() -> Webhooks {
+        Webhooks { retry_delay: Duration::from_millis(1), ..Webhooks::default() }
+    }
+
+    #[test]
+    fn templates() {
+        let payload = json!("Match accepted successfully!");
+
+        let generic: Value = serde_json::from_str(&render(WebhookFormat::Json, "match-accepted", &payload).body).unwrap();
+        assert_eq!(generic["event"], "match-accepted");
+        assert_eq!(generic["title"], "Match accepted");
+        assert_eq!(generic["message"], "Match accepted successfully!");
+        assert_eq!(generic["payload"], payload);
+
+        let discord = render(WebhookFormat::Discord, "match-accepted", &payload);
+        let discord: Value = serde_json::from_str(&discord.body).unwrap();
+        assert_eq!(discord["content"], "**Match accepted**\nMatch accepted successfully!");
+
+        let ntfy = render(WebhookFormat::Ntfy, "champion-pick-failed", &json!({ "message": "No champions available" }));
+        assert_eq!(ntfy.body, "No champions available");
+        assert_eq!(ntfy.content_type, "text/plain; charset=utf-8");
+        assert_eq!(ntfy.headers, vec![("Title", "Champion pick failed".to_string()), ("Tags", "warning".to_string())]);
+    }
+
+    #[test]
+    fn per_event_toggles() {
+        let mut config = hook("https://ntfy.sh/lol", WebhookFormat::Ntfy);
+        assert!(config.fires_on("match-accepted"));
+        assert!(!config.fires_on("champion-picked"));
+
+        config.enabled = false;
+        assert!(!config.fires_on("match-accepted"));
+    }
+
+    #[test]
+    fn urls_must_be_http() {
+        assert!(hook("https://discord.com/api/webhooks/1/abc", WebhookFormat::Discord).validate().is_ok());
+        assert!(hook("ftp://example.com/hook", WebhookFormat::Json).validate().is_err());
+        assert!(hook("not a url", WebhookFormat::Json).validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors_until_delivered() {
+        let (stand_in, url) = stand_in(&[503, 500]).await;
+
+        let result = webhooks().deliver(&hook(&url, WebhookFormat::Ntfy), "match-accepted", &json!("Accepted")).await;
+        assert!(result.is_ok());
+
+        let received = stand_in.received();
+        assert_eq!(received.len(), 3);
+        let (headers, body) = &received[2];
+        assert_eq!(headers["title"], "Match accepted");
+        assert_eq!(body, "Accepted");
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let (stand_in, url) = stand_in(&[500, 500, 500, 500]).await;
+
+        let error = webhooks().deliver(&hook(&url, WebhookFormat::Json), "match-accepted", &json!("Accepted")).await.unwrap_err();
+        assert!(error.to_string().contains("gave up after 3 attempts"), "{}", error);
+        assert_eq!(stand_in.requests(), 3);
+    }
+
+    #[tokio::test]
+    async fn rejected_requests_are_not_retried() {
+        let (stand_in, url) = stand_in(&[404]).await;
+
+        let result = webhooks().send_test(&hook(&url, WebhookFormat::Discord)).await;
+        assert!(result.unwrap_err().to_string().contains("404"));
+        assert_eq!(stand_in.requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn errors_leave_out_the_url() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook/secret-token", listener.local_addr().unwrap());
+        drop(listener);
+
+        let mut config = hook(&url, WebhookFormat::Json);
+        config.max_retries = 0;
+        let error = webhooks().deliver(&config, "match-accepted", &json!("Accepted")).await.unwrap_err();
+        assert!(error.to_string().starts_with("Could not reach webhook"), "{}", error);
+        assert!(!error.to_string().contains("secret-token"), "{}", error);
+    }
+}
@@ -6,29 +6,104 @@ import './App.css';
 interface ChampSelectConfig {
   auto_pick_enabled: boolean;
   auto_ban_enabled: boolean;
-  pick_priority: string[];
-  ban_priority: string[];
+  pick_priority: number[];
+  ban_priority: number[];
+  legacy_unresolved?: UnresolvedChampion[];
+}
+
+interface UnresolvedChampion {
+  setting: string;
+  name: string;
+  reason: string;
+}
+
+interface ChampionSummary {
+  id: number;
+  name: string;
+  alias: string;
+}
+
+interface CounterPickRule {
+  enemy: string;
+  lane: string | null;
+  prefer: string[];
+}
+
+interface ReadyCheckCountdown {
+  seconds_left: number;
+  queue_id: number | null;
+}
+
+interface PostGameProgress {
+  message: string;
+  games_played: number;
+  max_games: number;
+}
+
+interface InviteEvent {
+  inviter: string;
+  invitation_id: string;
+}
+
+interface DodgeRecord {
+  timestamp: string;
+  reason: string;
+  queue_id: number | null;
+  locked_in: boolean;
+}
+
+interface EntryReport {
+  input: string;
+  champion_id: number | null;
+  champion_name: string | null;
+  status: 'ok' | 'unknown' | 'ambiguous' | 'duplicate' | 'not_owned' | 'corrected';
+  message: string | null;
+}
+
+interface ValidationReport {
+  saved: boolean;
+  pick_priority: EntryReport[];
+  ban_priority: EntryReport[];
+}
+
+interface WaitingForClient {
+  message: string;
+  give_up_after_secs: number;
+}
+
+interface AutoAcceptState {
+  running: boolean;
+  auto_pick: boolean;
+  auto_ban: boolean;
+}
+
+interface ChampionPickedPayload {
+  message: string;
+  champion: string;
+  counter_rule: CounterPickRule | null;
 }
 
 function App() {
   const [isConnected, setIsConnected] = useState(false);
+  const [isWaitingForClient, setIsWaitingForClient] = useState(false);
   const [isMonitoring, setIsMonitoring] = useState(false);
   const [connectionStatus, setConnectionStatus] = useState('Checking League Client...');
   const [monitoringStatus, setMonitoringStatus] = useState('Disabled');
   const [isConnecting, setIsConnecting] = useState(false);
   const [isToggling, setIsToggling] = useState(false);
   const [isManualAccepting, setIsManualAccepting] = useState(false);
+  const [pendingAccept, setPendingAccept] = useState<number | null>(null);
   const [logs, setLogs] = useState([
     { time: new Date(), message: 'Application started. Click "Connect to League" to begin.', type: 'info' }
   ]);
 
   const [showSettings, setShowSettings] = useState(false);
-  const [availableChampions, setAvailableChampions] = useState<string[]>([]);
+  const [availableChampions, setAvailableChampions] = useState<ChampionSummary[]>([]);
   const [config, setConfig] = useState<ChampSelectConfig>({
     auto_pick_enabled: false,
     auto_ban_enabled: false,
-    pick_priority: ['Jinx', 'Ashe', 'Caitlyn'],
-    ban_priority: ['Yasuo', 'Zed', 'Master Yi']
+    pick_priority: [222, 22, 51],
+    ban_priority: [157, 238, 11]
   });
 
   const addLogEntry = (message: string, type: string = 'info') => {
@@ -79,15 +154,33 @@ function App() {
     const setupEventListeners = async () => {
       try {
         const unlistenMatchAccepted = await listen('match-accepted', (event) => {
+          setPendingAccept(null);
           addLogEntry(`🎉 ${event.payload}`, 'success');
         });
 
+        const unlistenMatchDeclined = await listen('match-declined', (event) => {
+          addLogEntry(`🛑 ${event.payload}`, 'info');
+        });
+
+        const unlistenReadyCheckSkipped = await listen('ready-check-skipped', (event) => {
+          addLogEntry(`⏸️ ${event.payload}`, 'info');
+        });
+
+        const unlistenCountdown = await listen<ReadyCheckCountdown>('ready-check-countdown', (event) => {
+          setPendingAccept(event.payload.seconds_left);
+        });
+
+        const unlistenCountdownCancelled = await listen('ready-check-cancelled', (event) => {
+          setPendingAccept(null);
+          addLogEntry(`✋ ${event.payload}`, 'info');
+        });
+
         const unlistenMatchFailed = await listen('match-accept-failed', (event) => {
           addLogEntry(`❌ ${event.payload}`, 'error');
         });
 
-        const unlistenChampionPicked = await listen('champion-picked', (event) => {
-          addLogEntry(`🎯 ${event.payload}`, 'success');
+        const unlistenChampionPicked = await listen<ChampionPickedPayload>('champion-picked', (event) => {
+          addLogEntry(`🎯 ${event.payload.message}`, 'success');
         });
 
         const unlistenChampionBanned = await listen('champion-banned', (event) => {
@@ -102,16 +195,81 @@ function App() {
           addLogEntry(`⚠️ ${event.payload}`, 'error');
         });
 
+        const unlistenWaiting = await listen<WaitingForClient>('waiting-for-client', (event) => {
+          const giveUpAt = new Date(Date.now() + event.payload.give_up_after_secs * 1000)
+            .toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' });
+          setIsConnected(false);
+          setIsWaitingForClient(true);
+          setConnectionStatus(`Waiting for League Client (gives up at ${giveUpAt})`);
+          addLogEntry(`⏳ ${event.payload.message}`, 'info');
+        });
+
+        const unlistenReconnected = await listen('league-reconnected', (event) => {
+          setIsConnected(true);
+          setIsWaitingForClient(false);
+          setConnectionStatus('Reconnected to League Client');
+          addLogEntry(`${event.payload}`, 'success');
+        });
+
         const unlistenDisconnected = await listen('league-disconnected', (event) => {
           setIsConnected(false);
+          setIsWaitingForClient(false);
           setConnectionStatus('League Client disconnected');
           addLogEntry(`⚠️ ${event.payload}`, 'error');
         });
 
+        const unlistenClientClosed = await listen('league-client-closed', (event) => {
+          setIsConnected(false);
+          setIsWaitingForClient(false);
+          setConnectionStatus('League Client closed');
+          addLogEntry(`⚠️ ${event.payload}`, 'info');
+        });
+
         const unlistenDelayNotice = await listen('auto-action-delay', (event) => {
           addLogEntry(`⏱️ ${event.payload}`, 'info');
         });
 
+        const unlistenPostGame = await Promise.all(
+          ['post-game-lobby', 'post-game-requeued', 'post-game-session-complete', 'post-game-failed'].map((name) =>
+            listen<PostGameProgress>(name, (event) => {
+              const { message, games_played, max_games } = event.payload;
+              const count = max_games > 0 ? `${games_played}/${max_games}` : `${games_played}`;
+              addLogEntry(`🔁 ${message} (games: ${count})`, name === 'post-game-failed' ? 'error' : 'info');
+            })
+          )
+        );
+
+        const unlistenInviteAccepted = await listen<InviteEvent>('party-invite-accepted', (event) => {
+          addLogEntry(`👥 Accepted party invite from ${event.payload.inviter}`, 'success');
+        });
+
+        const unlistenInviteDeclined = await listen<InviteEvent>('party-invite-declined', (event) => {
+          addLogEntry(`👥 Declined party invite from ${event.payload.inviter}`, 'info');
+        });
+
+        const unlistenQueueStarted = await listen('queue-started', (event) => {
+          addLogEntry(`🕹️ ${event.payload}`, 'success');
+        });
+
+        const unlistenQueueFailed = await listen('queue-failed', (event) => {
+          addLogEntry(`⚠️ ${event.payload}`, 'error');
+        });
+
+        const unlistenLastResortPick = await listen('champion-last-resort-picked', (event) => {
+          addLogEntry(`⏰ ${event.payload}`, 'success');
+        });
+
+        const unlistenDodged = await listen<DodgeRecord>('champ-select-dodged', (event) => {
+          addLogEntry(`🏃 Champion select ended: ${event.payload.reason}`, 'error');
+        });
+
+        const unlistenServiceState = await listen<AutoAcceptState>('auto-accept-state', (event) => {
+          const { running, auto_pick, auto_ban } = event.payload;
+          setIsMonitoring(running);
+          setMonitoringStatus(running ? 'Monitoring for matches, picks & bans...' : 'Disabled');
+          setConfig((current) => ({ ...current, auto_pick_enabled: auto_pick, auto_ban_enabled: auto_ban }));
+        });
+
         const unlistenAppReady = await listen('app-ready', () => {
           addLogEntry('Application ready. Checking for League Client...', 'info');
           connectToLeague();
@@ -119,13 +277,28 @@ function App() {
 
         return () => {
           unlistenMatchAccepted();
+          unlistenMatchDeclined();
+          unlistenReadyCheckSkipped();
+          unlistenCountdown();
+          unlistenCountdownCancelled();
           unlistenMatchFailed();
           unlistenChampionPicked();
           unlistenChampionBanned();
           unlistenPickFailed();
           unlistenBanFailed();
+          unlistenWaiting();
+          unlistenReconnected();
           unlistenDisconnected();
+          unlistenClientClosed();
           unlistenDelayNotice();
+          unlistenPostGame.forEach((unlisten) => unlisten());
+          unlistenInviteAccepted();
+          unlistenInviteDeclined();
+          unlistenQueueStarted();
+          unlistenQueueFailed();
+          unlistenLastResortPick();
+          unlistenDodged();
+          unlistenServiceState();
           unlistenAppReady();
         };
       } catch (error) {
@@ -144,6 +317,7 @@ function App() {
       const result = await invoke<string>('connect_to_league');
       
       setIsConnected(true);
+      setIsWaitingForClient(false);
       setConnectionStatus(result);
       addLogEntry(result, 'success');
       
@@ -160,7 +334,7 @@ function App() {
 
   const loadAvailableChampions = async () => {
     try {
-      const champions = await invoke<string[]>('get_all_champions');
+      const champions = await invoke<ChampionSummary[]>('get_all_champions');
       setAvailableChampions(champions);
     } catch (error) {
       console.error('Failed to load champions:', error);
@@ -171,6 +345,9 @@ function App() {
     try {
       const savedConfig = await invoke<ChampSelectConfig>('get_champ_select_config');
       setConfig(savedConfig);
+      savedConfig.legacy_unresolved?.forEach((entry) => {
+        addLogEntry(`⚠️ "${entry.name}" in ${entry.setting} from your old settings was not converted: ${entry.reason}`, 'error');
+      });
     } catch (error) {
       console.error('Failed to load config:', error);
     }
@@ -178,12 +355,25 @@ function App() {
 
   const saveChampSelectConfig = async () => {
     try {
-      await invoke('update_champ_select_config', {
+      const report = await invoke<ValidationReport>('update_champ_select_config', {
         autoPickEnabled: config.auto_pick_enabled,
         autoBanEnabled: config.auto_ban_enabled,
         pickPriority: config.pick_priority,
         banPriority: config.ban_priority
       });
+      [...report.pick_priority, ...report.ban_priority]
+        .filter((entry) => entry.status !== 'ok' && entry.message)
+        .forEach((entry) => {
+          const isError = entry.status === 'unknown' || entry.status === 'ambiguous';
+          addLogEntry(`${isError ? '❌' : '⚠️'} "${entry.input}": ${entry.message}`, isError ? 'error' : 'info');
+        });
+
+      if (!report.saved) {
+        addLogEntry('Settings not saved: fix the champions above and try again', 'error');
+        return;
+      }
+
+      await loadChampSelectConfig();
       addLogEntry('Settings saved successfully', 'success');
       setShowSettings(false);
     } catch (error) {
@@ -225,6 +415,17 @@ function App() {
     }
   };
 
+  const cancelPendingAccept = async () => {
+    try {
+      const result = await invoke<string>('cancel_pending_accept');
+      addLogEntry(result, 'info');
+    } catch (error) {
+      addLogEntry(`Cancel failed: ${error}`, 'error');
+    } finally {
+      setPendingAccept(null);
+    }
+  };
+
   const checkAutoAcceptStatus = async () => {
     try {
       const isRunning = await invoke<boolean>('is_auto_accept_running');
@@ -237,13 +438,16 @@ function App() {
     }
   };
 
-  const updatePickPriority = (index: number, champion: string) => {
+  const championName = (id: number) =>
+    availableChampions.find((champ) => champ.id === id)?.name ?? `#${id}`;
+
+  const updatePickPriority = (index: number, champion: number) => {
     const newPicks = [...config.pick_priority];
     newPicks[index] = champion;
     setConfig({ ...config, pick_priority: newPicks });
   };
 
-  const updateBanPriority = (index: number, champion: string) => {
+  const updateBanPriority = (index: number, champion: number) => {
     const newBans = [...config.ban_priority];
     newBans[index] = champion;
     setConfig({ ...config, ban_priority: newBans });
@@ -272,7 +476,7 @@ function App() {
             <div className="status-row">
               <div className="status-card">
                 <div className="status-indicator">
-                  <div className={`status-dot ${isConnected ? 'connected' : ''}`}></div>
+                  <div className={`status-dot ${isConnected ? 'connected' : isWaitingForClient ? 'waiting' : ''}`}></div>
                   <strong>League Client</strong>
                 </div>
                 <div className="status-text">{connectionStatus}</div>
@@ -295,7 +499,7 @@ function App() {
                 </div>
                 <div className="status-text">
                   {config.auto_pick_enabled 
-                    ? `${config.pick_priority.slice(0, 2).join(', ')}...` 
+                    ? `${config.pick_priority.slice(0, 2).map(championName).join(', ')}...` 
                     : 'Disabled'
                   }
                 </div>
@@ -308,7 +512,7 @@ function App() {
                 </div>
                 <div className="status-text">
                   {config.auto_ban_enabled 
-                    ? `${config.ban_priority.slice(0, 2).join(', ')}...` 
+                    ? `${config.ban_priority.slice(0, 2).map(championName).join(', ')}...` 
                     : 'Disabled'
                   }
                 </div>
@@ -317,6 +521,11 @@ function App() {
           </div>
 
           <div className="control-panel">
+            {pendingAccept !== null && (
+              <button className="btn btn-danger" onClick={cancelPendingAccept}>
+                Accepting in {pendingAccept}s — click to cancel
+              </button>
+            )}
             <div className="button-group">
               <button 
                 className="btn btn-secondary" 
@@ -390,12 +599,12 @@ function App() {
                         <span className="priority-number">{index + 1}.</span>
                         <select
                           value={champion}
-                          onChange={(e) => updatePickPriority(index, e.target.value)}
+                          onChange={(e) => updatePickPriority(index, Number(e.target.value))}
                           className="champion-select"
                         >
-                          <option value="">Select Champion</option>
+                          <option value={0}>Select Champion</option>
                           {availableChampions.map((champ) => (
-                            <option key={champ} value={champ}>{champ}</option>
+                            <option key={champ.id} value={champ.id}>{champ.name}</option>
                           ))}
                         </select>
                       </div>
@@ -423,12 +632,12 @@ function App() {
                         <span className="priority-number">{index + 1}.</span>
                         <select
                           value={champion}
-                          onChange={(e) => updateBanPriority(index, e.target.value)}
+                          onChange={(e) => updateBanPriority(index, Number(e.target.value))}
                           className="champion-select"
                         >
-                          <option value="">Select Champion</option>
+                          <option value={0}>Select Champion</option>
                           {availableChampions.map((champ) => (
-                            <option key={champ} value={champ}>{champ}</option>
+                            <option key={champ.id} value={champ.id}>{champ.name}</option>
                           ))}
                         </select>
                       </div>